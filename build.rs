@@ -0,0 +1,21 @@
+use std::process::Command;
+
+/// Pass the short hash of the built commit to the application, so the About
+/// window can show which build is running. Builds outside of a git repository,
+/// such as from a source archive, show "unknown".
+fn main() {
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_owned())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_owned());
+    println!("cargo:rustc-env=GIT_SHORT_HASH={}", hash);
+
+    // Build again when another commit is checked out or committed.
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+}
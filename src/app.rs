@@ -1,61 +1,355 @@
 mod about_window;
+mod announcer;
+#[cfg(not(target_arch = "wasm32"))]
+mod crash_save;
+#[cfg(not(target_arch = "wasm32"))]
+mod disk_cache;
+#[cfg(not(target_arch = "wasm32"))]
+mod equalizer;
+mod favicons;
+#[cfg(not(target_arch = "wasm32"))]
+mod headless;
+mod mirrors;
+mod notifications;
+mod player;
+mod profile;
+#[cfg(not(target_arch = "wasm32"))]
+mod recorder;
+#[cfg(not(target_arch = "wasm32"))]
+mod shortcut;
+mod station_window;
+mod translation;
+#[cfg(not(target_arch = "wasm32"))]
+mod vlc_media_player;
+mod volume;
+mod window;
+use crate::radio_browser::{
+    build_search_request, handle_response, is_station_uuid, is_valid_host, parse_vote_reply,
+    set_server, station_by_uuid_request, station_click_request, station_vote_request, Quality,
+    SearchBy, SearchOrder, SearchQuery, Station, MAX_LIMIT, SERVERS,
+};
 use about_window::AboutWindow;
+use announcer::Announcer;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crash_save::install_panic_hook;
 use eframe::egui;
-use serde::Deserialize;
+use favicons::FaviconCache;
+#[cfg(not(target_arch = "wasm32"))]
+pub use headless::play_headless;
+use mirrors::{stream_label, Failover, Mirrors};
+use notifications::Notifications;
+use player::{
+    PlatformPlayer, PlaybackState, Player, DEFAULT_METADATA_INTERVAL, METADATA_INTERVAL_RANGE,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use profile::profile_equalizer;
+use profile::{profile_of, profile_volume, StationProfile};
+use rand::seq::SliceRandom;
+use station_window::{homepage_button, tag_chips, StationWindow};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use web_sys::HtmlAudioElement;
+use translation::{t, tf};
+use volume::{max_volume, Volume};
 
 /// Enumerate the user interface languages.
 /// Debug and PartialEq are needed to print and use enums.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 /// It derives Deserialize/Serialize so it can persist app state on shutdown.
 #[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
 enum Language {
     English,
     Spanish,
     Russian,
+    /// Only partly translated, and falls back to English.
+    German,
 }
 
-/// The dtata associated to a radio station (url, name, etc).
-// Deriving the deserialization and serialization features is done by the
-// serde_json dependency. These derivations allow JSON text to be converted into
-// a Station struct.
-#[derive(Deserialize, Debug)]
-pub struct Station {
-    pub stationuuid: String,
-    pub name: String,
-    pub url: String,
-    pub url_resolved: String,
-    pub homepage: String,
-    pub favicon: String,
-    pub tags: String,
-    pub country: String,
-    pub state: String,
-    pub language: String,
-    pub votes: i32,
-    pub lastchangetime: String,
-    pub codec: String,
-    pub bitrate: u32,
-    pub lastcheckoktime: String,
-    pub clicktimestamp: String,
-    pub clickcount: u32,
-    pub clicktrend: i32,
+impl Language {
+    /// Every language, in the order they are shown.
+    const ALL: [Language; 4] = [
+        Language::English,
+        Language::Spanish,
+        Language::Russian,
+        Language::German,
+    ];
+}
+
+/// What the application should play when it starts, such as a station
+/// passed on the command line.
+#[derive(Debug, Default)]
+pub enum Startup {
+    /// Start paused.
+    #[default]
+    Nothing,
+    /// Play the given stream URL.
+    Url(String),
+    /// Retrieve the station with the given UUID from the database, and play it.
+    Station(String),
+}
+
+/// The orders in which the stations can be shown.
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+enum SortBy {
+    /// The radio browser order, with the preferred stations first.
+    Relevance,
+    /// The most voted stations first.
+    Votes,
+    /// The highest bitrate first.
+    Bitrate,
+    /// Alphabetically by name, ignoring case. Stations without a name last.
+    Name,
+    /// The most clicked stations first.
+    Clicks,
+}
+
+impl SortBy {
+    /// Every order, in the order they are shown.
+    const ALL: [SortBy; 5] = [
+        SortBy::Relevance,
+        SortBy::Votes,
+        SortBy::Bitrate,
+        SortBy::Name,
+        SortBy::Clicks,
+    ];
+
+    /// Return the English name of the order.
+    fn label(self) -> &'static str {
+        match self {
+            SortBy::Relevance => "Relevance",
+            SortBy::Votes => "Votes",
+            SortBy::Bitrate => "Bitrate",
+            SortBy::Name => "Name",
+            SortBy::Clicks => "Clicks",
+        }
+    }
+}
+
+/// How tightly the stations are laid out.
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+enum Density {
+    /// Every station is framed, with room between the stations.
+    Comfortable,
+    /// The stations are not framed, and are close together, so more of them
+    /// fit on the screen.
+    Compact,
+}
+
+impl Density {
+    /// Every density, in the order they are shown.
+    const ALL: [Density; 2] = [Density::Comfortable, Density::Compact];
+
+    /// Return the English name of the density.
+    fn label(self) -> &'static str {
+        match self {
+            Density::Comfortable => "Comfortable",
+            Density::Compact => "Compact",
+        }
+    }
+}
+
+/// The columns of the stations grid the user chose to show, besides the
+/// station itself, and whether the station logos are shown.
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+/// New fields are given default values when deserializing old state.
+#[cfg_attr(feature = "persistence", serde(default))]
+struct Columns {
+    /// Whether the station logos are shown next to the station names.
+    favicon: bool,
+    /// Whether the codec column is shown.
+    codec: bool,
+    /// Whether the bitrate column is shown.
+    bitrate: bool,
+    /// Whether the country column is shown.
+    country: bool,
+    /// Whether the tags column is shown.
+    tags: bool,
+    /// Whether the votes column is shown, with the button that votes.
+    votes: bool,
+}
+
+impl Default for Columns {
+    /// Show every column but the tags, which take the most room.
+    fn default() -> Self {
+        Self {
+            favicon: true,
+            codec: true,
+            bitrate: true,
+            country: true,
+            tags: false,
+            votes: true,
+        }
+    }
+}
+
+impl Columns {
+    /// Return whether every column is shown, with the English name of the
+    /// column, in the order they are shown.
+    fn toggles(&mut self) -> [(&mut bool, &'static str); 6] {
+        [
+            (&mut self.favicon, "Logo"),
+            (&mut self.codec, "Codec"),
+            (&mut self.bitrate, "Bitrate"),
+            (&mut self.country, "Country"),
+            (&mut self.tags, "Tags"),
+            (&mut self.votes, "Votes"),
+        ]
+    }
+}
+
+/// Whether the loaded station plays, which the play/pause button shows.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+enum Transport {
+    /// The station plays.
+    Playing,
+    /// The station is loaded, and plays again with the play/pause button.
+    Paused,
+    /// No station is loaded.
+    #[default]
+    Stopped,
+}
+
+impl Transport {
+    /// Return the icon of the play/pause button, which shows what clicking it
+    /// does: pause a playing station, or play it.
+    fn icon(self) -> char {
+        match self {
+            Transport::Playing => '⏸',
+            Transport::Paused | Transport::Stopped => '▶',
+        }
+    }
+
+    /// Return the English name of what the play/pause button does.
+    fn action(self) -> &'static str {
+        match self {
+            Transport::Playing => "Pause",
+            Transport::Paused | Transport::Stopped => "Play",
+        }
+    }
+
+    /// Pause the playing station, or play the loaded one, as the play/pause
+    /// button does, and return the new state.
+    fn toggle(self, media_player: &mut dyn Player, station_url: &str) -> Transport {
+        match self {
+            Transport::Playing => {
+                log::info!("Pausing {}", station_url);
+                media_player.pause();
+                Transport::Paused
+            }
+            // Play the URL again, so a live station resumes live instead of
+            // from the old buffer.
+            Transport::Paused | Transport::Stopped => {
+                log::info!("Resuming {}", station_url);
+                media_player.set_src(station_url);
+                media_player.play();
+                Transport::Playing
+            }
+        }
+    }
+
+    /// Stop the station, and return the new state, in which nothing is left
+    /// to resume.
+    fn stop(media_player: &mut dyn Player) -> Transport {
+        media_player.pause();
+        Transport::Stopped
+    }
+
+    /// Stop the playing station, if any, to load another one without playing
+    /// it, and return the new state. The play/pause button plays it.
+    fn select(media_player: &mut dyn Player) -> Transport {
+        media_player.pause();
+        Transport::Paused
+    }
+}
+
+/// A snapshot of what the user was browsing (the search and the filters),
+/// which can be restored on launch.
+#[derive(Debug, Default)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+/// New fields are given default values when deserializing old state.
+#[cfg_attr(feature = "persistence", serde(default))]
+struct ViewState {
+    /// The searched text, if there was a search.
+    query: Option<String>,
+    /// How many stations of the search were retrieved, counting the pages
+    /// loaded after the first one, or 0 if unknown.
+    retrieved: u32,
+    /// The homepage domain of the shown broadcaster, if any.
+    broadcaster_filter: Option<String>,
+    /// The shown state/region, if any.
+    state_filter: Option<String>,
+}
+
+/// The number of recently played stations that are remembered.
+const HISTORY_LIMIT: usize = 50;
+
+/// The narrowest and widest the user settings panel can be, so it can neither
+/// hide its own contents nor the stations.
+const SETTINGS_PANEL_WIDTHS: std::ops::RangeInclusive<f32> = 150.0..=400.0;
+
+/// How long after its last successful check a station is considered
+/// unreachable, in seconds.
+const UNREACHABLE_AFTER: i64 = 3 * 24 * 60 * 60;
+
+/// How often, in seconds, the volume is passed to the media player while the
+/// user drags it.
+const VOLUME_UPDATE_INTERVAL: f64 = 0.05;
+
+/// The codecs the search can be narrowed to.
+const CODECS: [&str; 5] = ["MP3", "AAC", "AAC+", "OGG", "FLAC"];
+
+/// The number of recent searches that are remembered.
+const SEARCH_HISTORY_LIMIT: usize = 20;
+
+/// How long, in seconds, the user must stop typing before searching, when
+/// searching as they type.
+const SEARCH_DELAY: f64 = 0.4;
+
+/// The most characters of a station name shown in the stations grid, so very
+/// long names do not widen the grid.
+const NAME_MAX_CHARS: usize = 60;
+
+/// The most characters of the tags shown in the stations grid.
+const TAGS_MAX_CHARS: usize = 40;
+
+/// What the downloaded stations are used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Fetch {
+    /// Replace the shown stations, such as for a new search.
+    Replace,
+    /// Add the next page of the current search to the shown stations.
+    Append,
+    /// Replace the shown stations with the first page of the current search,
+    /// downloaded again even if it was recently downloaded.
+    Refresh,
 }
 
 /// The download status.
 enum Download {
     /// No downloads done or in progress.
     None,
-    /// The download is in progress.
-    InProgress,
-    /// The download is done and the data is stored in the response, unless the
-    /// donwnload resulted in an error.
-    Done(Result<ehttp::Response, ehttp::Error>),
+    /// The download with the given number is in progress, since the given
+    /// time in seconds. The request is kept, so it can be retried if it takes
+    /// too long.
+    InProgress(Fetch, u64, ehttp::Request, f64),
+    /// The download is done, and the downloaded stations are stored.
+    Done(Fetch, Vec<Station>),
+    /// The request could not be sent or answered, such as when offline, or
+    /// the answer held no stations, for the given reason. The request is
+    /// kept, so it can be retried.
+    Failed(Fetch, ehttp::Request, String),
 }
 
 /// It derives Deserialize/Serialize so it can persist app state on shutdown.
 #[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
-/// New fields are are given default values when deserializing old state.
+/// New fields are given default values when deserializing old state.
 #[cfg_attr(feature = "persistence", serde(default))]
 pub struct App {
     // The name of the window.
@@ -74,420 +368,4473 @@ pub struct App {
     /// The station URL that will be streamed.
     station_url: String,
 
-    /// The string used to search for station names.
-    text_to_search: String,
+    /// The stations the user marked as favorite, without repetitions, in the
+    /// order the user chose.
+    favorites: Vec<Station>,
 
-    /// The volume level shown at all times.
-    volume_on_slider: i32,
+    /// The position of the favorite the user drags to move it, if any.
+    /// Opt-out of serialization for the dragged favorite.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    dragged_favorite: Option<usize>,
 
-    /// The volume level stored before muting the volume.
-    volume_before_mute: i32,
+    /// The recently played stations, the most recent first, without
+    /// repetitions.
+    history: VecDeque<Station>,
 
-    /// The About window shown in the menu bar.
-    about_window: AboutWindow,
+    /// The recently searched text, the most recent first, without
+    /// repetitions.
+    search_history: VecDeque<String>,
 
-    /// Opt-out of serialization for the Web-sys media player.
+    /// The station logos that were downloaded.
+    /// Opt-out of serialization for the logos.
     #[cfg_attr(feature = "persistence", serde(skip))]
-    media_player: HtmlAudioElement,
+    favicons: FaviconCache,
 
-    /// Wether an station is playing or not.
-    playing_icon: char,
+    /// The string used to search for station names.
+    text_to_search: String,
 
-    /// Wether the user settings panel is open or not.
-    user_settings_is_open: bool,
+    /// The station field searched with the search bar, such as the name.
+    search_by: SearchBy,
 
-    /// The user interface language.
-    language: Language,
-}
+    /// The lowest bitrate of the searched stations in kbps, or 0 for any.
+    min_bitrate: u32,
 
-/// Implement trait to create default window.
-impl App {
-    /// Create default window.
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        // Initial media player volume.
-        let volume = 50;
-        App {
-            /// Name the application (the main window).
-            name: "Online Radio".to_owned(),
-            /// Initially there are no downloads.
-            download: Arc::new(Mutex::new(Download::None)),
+    /// The codec of the searched stations, or empty for any codec.
+    codec_filter: String,
 
-            // Initially the list of stations is empty.
-            stations: Arc::new(Mutex::new(Vec::new())),
+    /// The tag, country, and language the searched stations must have, when
+    /// not searched by them. Empty texts match every station.
+    search_tag: String,
+    search_country: String,
+    search_language: String,
 
-            /// By default play a dubstep station.
-            station_url: "https://ice5.somafm.com/dubstep-128-mp3".to_owned(),
+    /// The order in which the radio browser returns the searched stations.
+    search_order: SearchOrder,
 
-            /// Initially there is no text to search.
-            text_to_search: "".to_owned(),
+    /// The order in which the stations are shown.
+    sort_by: SortBy,
 
-            /// Set the initial slider volume.
-            volume_on_slider: volume,
+    /// The volume level and mute state shown at all times.
+    volume: Volume,
 
-            /// Set the initial volume before muting.
-            volume_before_mute: volume,
+    /// When the volume was last passed to the media player while dragging,
+    /// in seconds.
+    /// Opt-out of serialization for the volume update time.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    volume_sent_at: f64,
 
-            /// Creates a default About window.
-            about_window: AboutWindow::default(),
+    /// Whether the volume changed since it was last passed to the media
+    /// player.
+    /// Opt-out of serialization for the pending volume.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    volume_is_pending: bool,
 
-            /// Use Web-sys for playing URLs when compiling webassembly.
-            media_player: HtmlAudioElement::new().unwrap(),
+    /// The About window shown in the menu bar.
+    about_window: AboutWindow,
 
-            // Set the playing icon as the default icon.
-            playing_icon: '▶',
+    /// The window that shows the details of a station.
+    /// Opt-out of serialization for the station window.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    station_window: StationWindow,
 
-            /// The user settings panel should be closed by default.
-            user_settings_is_open: false,
+    /// Plays the stations with Web-sys on the web, and with VLC natively.
+    /// Opt-out of serialization for the media player.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    media_player: Box<dyn Player>,
 
-            /// Set the default language to English.
-            language: Language::English,
-        }
-    }
-}
+    /// Whether the loaded station plays, is paused, or there is none.
+    /// Opt-out of serialization for the transport, since the media player
+    /// does not play on launch.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    transport: Transport,
 
-/// Define function for running app natively and on web.
-impl eframe::App for App {
-    /// Called by the frame work to save state before shutdown.
-    /// Note that you must enable the `persistence` feature for this to work.
-    #[cfg(feature = "persistence")]
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
-    }
+    /// The name of the playing station, or its URL if the name is unknown.
+    /// It is saved, so the loaded station is shown on the next launch.
+    now_playing: String,
 
-    /// Called each time the UI needs repainting, which may be many times per
-    /// second.  Put your widgets into a `SidePanel`, `TopPanel`,
-    /// `CentralPanel`, `Window` or `Area`.
-    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
-        let Self {
-            name,
-            download,
-            stations,
-            station_url,
-            text_to_search,
-            volume_on_slider,
-            volume_before_mute,
-            about_window,
-            media_player,
-            playing_icon,
-            user_settings_is_open,
-            language,
-        } = self;
+    /// The playing station, if it was played from the database rather than
+    /// from a URL. It is saved, so it can be voted for on the next launch.
+    playing_station: Option<Station>,
 
-        // Show the about window when the menu item is pressed.
-        about_window.update(ctx, frame);
+    /// The UUIDs of the stations voted for during this session, since each
+    /// station may only be voted for once.
+    /// Opt-out of serialization for the votes.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    voted: HashSet<String>,
 
-        // Examples of how to create different panels and windows.
-        // Pick whichever suits you.
-        // Tip: a good default choice is to just keep the `CentralPanel`.
-        // For inspiration and more examples, go to https://emilk.github.io/egui
+    /// Whether the stations show checkboxes, to act on many stations at once.
+    /// Opt-out of serialization for the selection mode.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    selection_mode: bool,
 
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            // The top panel is often a good place for a menu bar:
-            egui::menu::bar(ui, |ui| {
-                // Add theme switch in menu bar.
-                egui::global_dark_light_mode_switch(ui);
-                // Add a menu bar category for the current file/page.
-                ui.menu_button("File", |ui| {
-                    // Add a menu item for quitting the application.
-                    if ui.button("Quit").clicked() {
-                        frame.quit();
-                    }
-                });
+    /// The UUIDs of the stations checked in the selection mode.
+    /// Opt-out of serialization for the checked stations.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    checked_stations: HashSet<String>,
 
-                // Add a menu bar category for showing iformation about the app.
-                ui.menu_button("Help", |ui| {
-                    // Add a menu item for shoowing the information.
-                    if ui.button("About").clicked() {
-                        // Toggle the window on and off.
-                        self.about_window.is_open = !self.about_window.is_open;
-                    }
-                });
-            });
-        });
+    /// The UUIDs of the stations whose votes were not counted, and why.
+    /// Opt-out of serialization for the rejected votes.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    rejected_votes: Arc<Mutex<Vec<(String, String)>>>,
 
-        // Add a search panel to look for station names and access user
-        // settings.
-        egui::TopBottomPanel::top("search_panel").show(ctx, |ui| {
-            // All the widgets should be placed in the same horizontal line, as
-            // opposed to one below the other.
-            ui.horizontal(|ui| {
-                // Show the website name.
-                ui.heading(name.to_string());
+    /// Tells screen readers when the now-playing text changes.
+    /// Opt-out of serialization for the announcer.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    announcer: Announcer,
 
-                // Create a flag that triggers the download of station from the
-                // database.
-                let mut trigger_fetch = false;
+    /// Whether the user settings panel is open or not.
+    user_settings_is_open: bool,
 
-                // Add magnifying glass that triggers radio station search.
-                trigger_fetch |= ui.button("🔍").clicked();
+    /// Whether the options panel is open or not.
+    options_is_open: bool,
 
-                // Calculate the button width. This will be used for spacing.
-                let button_width = ui.spacing().interact_size.x;
-                // Calculate the available width. This will be used for spacing.
-                let width = ui.available_width();
-                // Add a search bar to search for stations. Adjust search bar
-                // width based on available wdith.
-                let search = ui.add(
-                    egui::TextEdit::singleline(text_to_search)
-                        .desired_width(width - button_width * 1.6)
-                        .hint_text("Search…"),
-                );
+    /// The width of the user settings panel chosen by the user.
+    settings_panel_width: f32,
 
-                // The search bar triggers a radio station search whenever the
-                // user presses "Enter".
-                trigger_fetch |= search.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+    /// The volume level set on launch, or None to keep the last volume.
+    default_volume: Option<i32>,
 
-                if trigger_fetch {
-                    // Search stations by name.
-                    // TODO: Use post method to specify more than one parameter.
-                    // TODO: Randomly choose a radio browser server to distribute load.
-                    let request = ehttp::Request::get(format!(
-                        "https://de1.api.radio-browser.info/json/stations/byname/{}?limit=100",
-                        text_to_search
-                    ));
+    /// The maximum number of stations retrieved by a search.
+    results_limit: u32,
 
-                    // Create a copy of the download that will be moved to another thread.
-                    let download_store = download.clone();
+    /// How long a search may take before it is abandoned, in seconds.
+    request_timeout: u32,
 
-                    // Set the download in progress.
-                    *download_store.lock().unwrap() = Download::InProgress;
-                    // Fetch the request, and when done, process the response.
-                    ehttp::fetch(request, move |response| {
-                        // Set the download as done, and store the response.
-                        *download_store.lock().unwrap() = Download::Done(response);
-                    });
-                }
+    /// The host of the radio browser server chosen by the user, or empty to
+    /// use a random server.
+    server: String,
 
-                // Add a login button.
-                if ui.button("👤").clicked() {
-                    // This flag is used inside the central panel to draw the
-                    // side panel. The side panel must be drawn inside the
-                    // central body, or it will interfere with the top and
-                    // bottom panels.
-                    *user_settings_is_open = !*user_settings_is_open;
-                }
+    /// The server host typed by the user.
+    /// Opt-out of serialization for the typed host.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    server_input: String,
 
-                // Add an options button.
-                if ui.button("☰").clicked() {}
-            });
-        });
+    /// Whether the typed host was submitted and is not a host.
+    /// Opt-out of serialization for the host error.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    server_input_is_invalid: bool,
 
-        // Create a bottom pannel. The top/bottom/side panels must be drawn
-        // before the central panel.
-        egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
-            // Display artist and song name.
-            ui.label("Artist Name - Song Name");
+    /// The id of the audio device to play through natively, or empty for the
+    /// default device.
+    audio_device: String,
 
-            // Separate the artist and song names from the buttons.
-            ui.separator();
+    /// Whether to fade from the playing station to the next one natively.
+    crossfade: bool,
 
-            ui.horizontal(|ui| {
-                // Toggle play/pause when the play/pause icon is clicked.
-                if ui.button(playing_icon.to_string()).clicked() {
-                    // Chose correct playing icon and playing state based on the icon.
-                    // The logic seems reversed here, but it is really not.
-                    *playing_icon = match playing_icon {
-                        // If not playing, show the play button.
-                        '⏸' => {
-                            let _ = media_player.pause();
-                            '▶'
-                        }
-                        // If playing, show the pause button and play the URL.
-                        '▶' => {
-                            media_player.set_src(station_url);
-                            let _ = media_player.play();
-                            '⏸'
-                        }
-                        // Return the same icon.
-                        _ => *playing_icon,
-                    }
-                }
+    /// Whether the stations ease in to the volume when they start playing
+    /// natively.
+    fade_in: bool,
 
-                // Chose correct volume icon based on volume level.
-                let volume_icon = match volume_on_slider {
-                    // If volume is 0:
-                    0 => "🔇",
-                    // If volume is between 1 and 30:
-                    1..=30 => "🔈",
-                    // If volume is between 31 and 70:
-                    31..=70 => "🔉",
-                    // For any other value:
-                    _ => "🔊",
-                };
+    /// How often, in seconds, the title of the playing track is asked for
+    /// natively.
+    metadata_interval: u32,
 
-                // Toggle volume on and off when volume icon is clicked.
-                if ui.button(volume_icon).clicked() {
-                    // If the volume is not mute:
-                    if *volume_on_slider != 0 {
-                        // Store the current volume level.
-                        *volume_before_mute = *volume_on_slider;
-                        // Mute the volume.
-                        *volume_on_slider = 0;
-                    } else {
-                        // Else the volume is mute.
-                        // Retrieve the last current volume level.
-                        *volume_on_slider = *volume_before_mute;
-                    }
-                    // Web-sys takes volme as a float in the range 0.0 to 1.0.
-                    media_player.set_volume(*volume_on_slider as f64 / 100.0);
-                }
+    /// Whether the volume may go above 100% natively, to amplify quiet
+    /// stations.
+    volume_boost: bool,
 
-                // Display a volume slider, and change the volume when the
-                // slider is clicked or dragged.
-                if ui
-                    .add(egui::Slider::new(volume_on_slider, 0..=100).show_value(false))
-                    .is_pointer_button_down_on()
-                {
-                    // Web-sys takes volme as a float in the range 0.0 to 1.0.
-                    media_player.set_volume(*volume_on_slider as f64 / 100.0);
-                }
+    /// Whether the volume the user chose for a station is restored when the
+    /// station plays again, since some stations are louder than others.
+    remember_station_volumes: bool,
 
-                /*
-                // Calculate the button width. This will be used for spacing.
-                let button_width = ui.spacing().interact_size.x;
-                // Calculate the available width.
-                let width = ui.available_width();
+    /// The volume the user chose for each station, by station UUID.
+    station_volumes: HashMap<String, i32>,
 
-                // let x = ui.spacing().interact_size.x * 1.5 + ui.spacing().slider_width
-                // ui.add_space(width - 4.0 * button_width);
+    /// The volume offset and equalizer preset of each station, by station
+    /// UUID, which are applied when the station plays.
+    station_profiles: HashMap<String, StationProfile>,
 
-                // TODO: add more functionality and make consider small phone screen sizes.
-                // Also, vote for each station whenever it is played.
-                // Add button that copies artist and song name.
-                if ui.button("📋").clicked() {
-                    // Copy song title.
-                }
-                // Add button that adds current station to preferred stations.
-                if ui.button("➕♫").clicked() {}
-                if ui.button("➕🎵").clicked() {}
-                if ui.button("🎶").clicked() {}
-                // Add an options button.
-                if ui.button("📻").clicked() {}
-                */
-            });
-        });
+    /// The streams of the playing station, which are tried in turn when the
+    /// playing stream fails.
+    /// Opt-out of serialization for the streams.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    mirrors: Mirrors,
 
-        // The central panel is the region left after adding top and
-        // side panels.
-        egui::CentralPanel::default().show(ctx, |ui| {
-            egui::warn_if_debug_build(ui);
+    /// The URL of the stream that last played, by station UUID, for the
+    /// stations whose own stream failed, or that the user played at another
+    /// quality. The station starts with that stream the next time.
+    preferred_streams: HashMap<String, String>,
 
-            // Create a download guard.
-            let mut download_inner = download.lock().unwrap();
+    /// The equalizer applied to the stations played natively.
+    #[cfg(not(target_arch = "wasm32"))]
+    equalizer: equalizer::Equalizer,
 
-            // Match the donwload state.
-            match &*download_inner {
-                // If no download, do nothing.
-                Download::None => {}
-                // If download in progress, show message.
-                Download::InProgress => {
-                    ui.label("Retrieving stations…");
-                }
-                // If the HTTP response is OK, process the text.
-                Download::Done(Ok(response)) => match response.text() {
-                    // If there is text, try to convert it into a vector of stations.
-                    Some(text) => match serde_json::from_str::<Vec<Station>>(&text) {
-                        // If the conversion is ok, save the vector of stations.
-                        Ok(stations_vector) => {
-                            // Store the stations.
-                            // TODO: Remove stations with same names and urls.
-                            *stations.lock().unwrap() = stations_vector;
-
-                            // Show there are no more downloads.
-                            *download_inner = Download::None;
-                        }
-                        // If the conversion produced an error, show the error message.
-                        Err(e) => {
-                            ui.label(e.to_string());
-                        }
-                    },
-                    // If there is no text, show a message.
-                    None => {
-                        ui.label("No stations.");
-                    }
-                },
-                // If the HTTP response had an error, show error message.
-                Download::Done(Err(err)) => {
-                    ui.label(err);
-                }
-            }
+    /// The directory the recordings are saved to natively, or empty for the
+    /// music directory of the user.
+    #[cfg(not(target_arch = "wasm32"))]
+    recordings_dir: String,
 
-            // Add a scroll area so the user can scroll through the stations.
-            egui::ScrollArea::vertical()
-                .max_width(f32::INFINITY)
-                .show(ui, |ui| {
-                    // Add a grid where the stations will be placed.
-                    egui::Grid::new("stations")
-                        .striped(true)
-                        .min_col_width(200.0)
-                        .show(ui, |ui| {
-                            // For every URL in the vector:
-                            for station in &*stations.lock().unwrap() {
-                                // Create a group of components that will represent a link to a station.
-                                ui.group(|ui| {
-                                    // Place the widgets horizontally.
-                                    ui.horizontal(|ui| {
-                                        // Add a play button for the station.
-                                        if ui.button("▶").clicked() {
-                                            // Update the playing icon.
-                                            *playing_icon = '⏸';
+    /// Records the playing station natively, when asked to.
+    /// Opt-out of serialization for the recorder.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    recorder: recorder::Recorder,
 
-                                            // Get the station URL to be streamed.
-                                            *station_url = station.url_resolved.to_string();
+    /// Whether to play a station when the application starts.
+    autoplay_on_launch: bool,
 
-                                            // Pass the URL to the station.
-                                            media_player.set_src(station_url);
+    /// The station played when the application starts, or None to play the
+    /// last played station.
+    default_station: Option<Station>,
 
-                                            // Stop the station in case it is playing.
-                                            let _ = media_player.pause();
+    /// Whether to search once the user stops typing, without pressing Enter.
+    search_as_you_type: bool,
 
-                                            // Play the station.
-                                            // TODO: Allow player to play HTTP stations, not only HTTPS.
-                                            let _ = media_player.play();
+    /// Whether the play button of a station in the results plays it right
+    /// away, or only selects it, to be played with the play/pause button.
+    play_on_click: bool,
+
+    /// How tightly the stations are laid out.
+    density: Density,
+
+    /// The columns of the stations grid that are shown.
+    columns: Columns,
+
+    /// When the search text was last edited, in seconds, if it was not
+    /// searched yet.
+    /// Opt-out of serialization for the edit time.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    search_edited_at: Option<f64>,
+
+    /// The user interface language.
+    language: Language,
+
+    /// Whether the user interface uses the dark theme, or the light one.
+    dark_mode: bool,
+
+    /// Whether the radio browser should leave out the stations it knows are
+    /// broken.
+    hide_broken: bool,
+
+    /// Whether to hide the stations that did not pass a check of the radio
+    /// browser recently.
+    hide_unreachable: bool,
+
+    /// Whether to hide the stations that do not stream over HTTPS.
+    https_only: bool,
+
+    /// The names of the stations the user marked as broken, by station UUID,
+    /// which are never shown in the results.
+    blocked_stations: BTreeMap<String, String>,
+
+    /// The country whose stations are shown first, if not empty.
+    preferred_country: String,
+
+    /// The language whose stations are shown first, if not empty.
+    preferred_language: String,
+
+    /// The two-letter code of the country whose stations the local search
+    /// finds, detected on the first local search or entered by the user.
+    local_country_code: String,
+
+    /// The homepage domain used to show only stations from one broadcaster.
+    /// Opt-out of serialization for the broadcaster filter.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    broadcaster_filter: Option<String>,
+
+    /// The state/region used to narrow stations from a single country. An
+    /// empty state shows the stations with an unknown state.
+    /// Opt-out of serialization for the state filter.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    state_filter: Option<String>,
+
+    /// The countries whose stations are shown, or none to show every country.
+    /// Opt-out of serialization for the country filter.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    country_facets: BTreeSet<String>,
+
+    /// The tags whose stations are shown, or none to show every tag.
+    /// Opt-out of serialization for the tag filter.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    tag_facets: BTreeSet<String>,
+
+    /// The messages shown to the user for a few seconds, such as the result
+    /// of an action, or why it failed.
+    /// Opt-out of serialization for the notifications.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    notifications: Notifications,
+
+    /// Why the station could not be played, once the user was told.
+    /// Opt-out of serialization for the reported playback error.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    reported_playback_error: Option<String>,
+
+    /// What to play when the first frame is shown.
+    /// Opt-out of serialization for the startup intent.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    startup: Startup,
+
+    /// Whether to play the first station once the current download is done.
+    /// Opt-out of serialization for the autoplay flag.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    play_first_result: bool,
+
+    /// The last search, with the offset of the page shown last, if any.
+    /// Opt-out of serialization for the current search.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    current_query: Option<SearchQuery>,
+
+    /// Whether the current search may have more stations than are shown.
+    /// Opt-out of serialization for the more results flag.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    more_results: bool,
+
+    /// The text of the last search, if any.
+    /// Opt-out of serialization for the searched text.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    searched_text: Option<String>,
+
+    /// Whether to restore the last view (search and filters) on launch.
+    restore_last_view: bool,
+
+    /// The view saved on shutdown, which is restored on the first frame.
+    last_view: Option<ViewState>,
+
+    /// Whether the dialog that confirms resetting the settings is open or not.
+    /// Opt-out of serialization for the reset dialog.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    reset_dialog_is_open: bool,
+
+    /// Whether resetting the settings also clears the favorites and the
+    /// history.
+    /// Opt-out of serialization for the reset choice.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    reset_clears_saved: bool,
+
+    /// Whether the dialog that plays a stream URL is open or not.
+    /// Opt-out of serialization for the play URL dialog.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    play_url_dialog_is_open: bool,
+
+    /// The stream URL typed in the play URL dialog.
+    /// Opt-out of serialization for the typed URL.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    url_to_play: String,
+
+    /// Whether the typed URL was submitted and could not be played.
+    /// Opt-out of serialization for the URL error.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    url_is_invalid: bool,
+
+    /// Whether the user chose to quit during this frame.
+    /// Opt-out of serialization for the quit request.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    quit_requested: bool,
+
+    /// The title of the track after which the station pauses, if the user
+    /// chose to stop after it.
+    /// Opt-out of serialization for the armed stop.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    stop_after_track: Option<String>,
+}
+
+/// How long the downloaded stations are reused natively, instead of searching
+/// again.
+#[cfg(not(target_arch = "wasm32"))]
+const STATIONS_CACHE_AGE: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// Fetch the request in the background, and store the response in the
+/// download when done, repainting the user interface so the response shows
+/// right away. Natively, recent responses are read from the disk instead. If
+/// the request fails, the user is notified in the given language. The
+/// response is dropped if another download started meanwhile, so the
+/// stations of an older search never replace newer ones.
+fn fetch_stations(
+    ctx: &egui::Context,
+    download: &Arc<Mutex<Download>>,
+    notifications: &Notifications,
+    lang: Language,
+    request: ehttp::Request,
+    fetch: Fetch,
+) {
+    // Number the downloads, so each response can tell whether it is stale.
+    static DOWNLOADS_STARTED: AtomicU64 = AtomicU64::new(0);
+    let number = DOWNLOADS_STARTED.fetch_add(1, Ordering::Relaxed);
+
+    // Create a copy of the download that will be moved to another thread.
+    let download_store = download.clone();
+
+    // Keep a copy of the request, to retry it if it fails.
+    let retry = copy_request(&request);
+
+    // Create a copy of the context, to repaint once the response arrives, and
+    // of the notifications, to tell the user if the request fails.
+    let ctx = ctx.clone();
+    let notifications = notifications.clone();
+
+    // Use the stored response, if the same stations were recently downloaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    if request.method == "GET" && fetch != Fetch::Refresh {
+        if let Some(bytes) = disk_cache::read("stations", &request.url, STATIONS_CACHE_AGE) {
+            let response = ehttp::Response {
+                url: request.url.clone(),
+                ok: true,
+                status: 200,
+                status_text: "OK".to_owned(),
+                bytes,
+                headers: Default::default(),
+            };
+            // Download the stations again if the stored ones are damaged.
+            if let Ok(stations) = handle_response(Ok(response)) {
+                log::debug!("Using the stored stations of {}", request.url);
+                *download_store.lock().unwrap() = Download::Done(fetch, stations);
+                return;
+            }
+        }
+    }
+
+    // Set the download in progress, since now.
+    let started_at = ctx.input().time;
+    *download_store.lock().unwrap() =
+        Download::InProgress(fetch, number, copy_request(&request), started_at);
+    // Fetch the request, and when done, process the response.
+    log::debug!("{} {}", request.method, request.url);
+    ehttp::fetch(request, move |response| {
+        // Store the stations, so the same search can be shown again without
+        // downloading them.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Ok(response) = &response {
+            if response.ok && retry.method == "GET" {
+                disk_cache::write("stations", &retry.url, &response.bytes);
+            }
+        }
+
+        // Convert the response into stations away from the user interface.
+        let stations = handle_response(response).map_err(|err| err.to_string());
+
+        // Set the download as done, and store the stations, unless a newer
+        // download replaced this one.
+        let mut download = download_store.lock().unwrap();
+        if matches!(*download, Download::InProgress(_, latest, _, _) if latest == number) {
+            *download = match stations {
+                Ok(stations) => {
+                    log::debug!("Retrieved {} stations from {}", stations.len(), retry.url);
+                    Download::Done(fetch, stations)
+                }
+                Err(err) => {
+                    log::warn!("Could not retrieve stations from {}: {}", retry.url, err);
+                    notifications.error(t(lang, friendly_error(&err)));
+                    Download::Failed(fetch, retry, err)
+                }
+            };
+            ctx.request_repaint();
+        }
+    });
+}
+
+/// Copy the request, which cannot be cloned.
+fn copy_request(request: &ehttp::Request) -> ehttp::Request {
+    ehttp::Request {
+        method: request.method.clone(),
+        url: request.url.clone(),
+        body: request.body.clone(),
+        headers: request.headers.clone(),
+    }
+}
+
+/// Return a message the user can act on for the error of a request that could
+/// not be sent or answered. The errors differ by platform, so they are
+/// recognized by the words they contain.
+fn friendly_error(error: &str) -> &'static str {
+    let error = error.to_lowercase();
+    let offline_words = [
+        "dns",
+        "resolve",
+        "connect",
+        "network",
+        "failed to fetch",
+        "unreachable",
+    ];
+    if error.contains("timed out") {
+        "The search took too long — check your network and try again."
+    } else if offline_words.iter().any(|word| error.contains(word)) {
+        "No internet connection — check your network and try again."
+    } else {
+        "Could not reach the radio browser. Try again later."
+    }
+}
+
+/// Pause the station once the track the user chose to stop after ends, which
+/// is when the station sends another title, and disarm the stop. The stop is
+/// also disarmed once the station no longer plays.
+fn stop_after_song(
+    media_player: &mut dyn Player,
+    transport: &mut Transport,
+    stop_after_track: &mut Option<String>,
+) {
+    let armed = match stop_after_track {
+        Some(title) => title,
+        None => return,
+    };
+    if *transport != Transport::Playing {
+        *stop_after_track = None;
+    } else if media_player
+        .track_title()
+        .is_some_and(|title| title != *armed)
+    {
+        log::info!("Stopping after {}", armed);
+        media_player.pause();
+        *transport = Transport::Paused;
+        *stop_after_track = None;
+    }
+}
+
+/// Return the URL of the stream the station last played with, if another
+/// stream than its own played, or else its own stream.
+fn preferred_stream<'a>(
+    preferred_streams: &'a HashMap<String, String>,
+    station: &'a Station,
+) -> &'a str {
+    preferred_streams
+        .get(&station.stationuuid)
+        .map_or(station.stream_url(), String::as_str)
+}
+
+/// Remember the URL of the stream the station plays with, if it is not its
+/// own stream, so the station starts with it the next time.
+fn prefer_stream(preferred_streams: &mut HashMap<String, String>, station: &Station, url: &str) {
+    if url == station.stream_url() {
+        preferred_streams.remove(&station.stationuuid);
+    } else if preferred_streams
+        .get(&station.stationuuid)
+        .map(String::as_str)
+        != Some(url)
+    {
+        preferred_streams.insert(station.stationuuid.clone(), url.to_owned());
+    }
+}
+
+/// Return the dark theme, or the light theme.
+fn theme(dark_mode: bool) -> egui::Visuals {
+    if dark_mode {
+        egui::Visuals::dark()
+    } else {
+        egui::Visuals::light()
+    }
+}
+
+/// Pass the volume and the equalizer of the profile of the station about to
+/// play to the media player, when they differ from those of the previous
+/// station's profile. The volume is passed anyway if it was restored.
+#[allow(clippy::too_many_arguments)]
+fn switch_profile(
+    media_player: &mut dyn Player,
+    volume: &Volume,
+    max: i32,
+    #[cfg(not(target_arch = "wasm32"))] equalizer: &equalizer::Equalizer,
+    from: Option<&StationProfile>,
+    to: Option<&StationProfile>,
+    volume_restored: bool,
+) {
+    let level = profile_volume(volume.on_slider, to, max);
+    if volume_restored || level != profile_volume(volume.on_slider, from, max) {
+        media_player.set_volume(level);
+    }
+    // Changing the equalizer restarts VLC, so only change it if needed.
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let new_equalizer = profile_equalizer(equalizer, to);
+        if new_equalizer != profile_equalizer(equalizer, from) {
+            media_player.set_equalizer(&new_equalizer);
+        }
+    }
+}
+
+/// Pass the URL to the media player, and play it. The playing station, if
+/// any, is replaced by the player, so it can fade out as the new one starts.
+fn play_url(media_player: &mut dyn Player, url: &str) {
+    log::info!("Playing {}", url);
+    // Pass the URL to the station.
+    media_player.set_src(url);
+
+    // Play the station.
+    media_player.play();
+}
+
+/// Return the current time in seconds since the Unix epoch. The standard
+/// library cannot tell the time on the web, so the browser is asked instead.
+fn unix_time() -> i64 {
+    #[cfg(target_arch = "wasm32")]
+    let seconds = js_sys::Date::now() / 1000.0;
+    #[cfg(not(target_arch = "wasm32"))]
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+    seconds as i64
+}
+
+/// Return the two-letter code of the country of the user, such as "DE", from
+/// the language settings, which name the region, such as "de_DE.UTF-8" or
+/// "de-DE". The location is not looked up online, so the country is unknown
+/// if the settings do not name a region.
+fn detect_country_code() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    let locale = web_sys::window()?.navigator().language()?;
+    #[cfg(not(target_arch = "wasm32"))]
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())?;
+    region_of_locale(&locale)
+}
+
+/// Return the region of the locale, such as "DE" for "de_DE.UTF-8", "de-DE",
+/// or "de_DE@euro", if it names one.
+fn region_of_locale(locale: &str) -> Option<String> {
+    let language_and_region = locale.split(['.', '@']).next()?;
+    let region = language_and_region.split(['_', '-']).nth(1)?;
+    (region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| region.to_ascii_uppercase())
+}
+
+/// Return the link that opens the application playing the station with the
+/// given UUID, on the page the application runs on.
+#[cfg(target_arch = "wasm32")]
+fn share_link(uuid: &str) -> Option<String> {
+    let location = web_sys::window()?.location();
+    let origin = location.origin().ok()?;
+    let path = location.pathname().ok()?;
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("station", uuid)
+        .finish();
+    Some(format!("{}{}?{}", origin, path, query))
+}
+
+/// Return whether the text is an HTTP or HTTPS URL, which both the browser and
+/// VLC can stream.
+fn is_stream_url(text: &str) -> bool {
+    url::Url::parse(text.trim())
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
+}
+
+/// Tell the radio browser the station was played. The response is ignored,
+/// so a failure never stops the station from playing.
+fn register_click(station: &Station) {
+    ehttp::fetch(station_click_request(&station.stationuuid), |_| {});
+}
+
+/// Vote for the station with the given UUID in the background. If the vote is
+/// not counted, the UUID and the reason are added to the rejected votes.
+fn send_vote(ctx: &egui::Context, uuid: &str, rejected_votes: &Arc<Mutex<Vec<(String, String)>>>) {
+    let uuid = uuid.to_owned();
+    let rejected_votes = rejected_votes.clone();
+    let ctx = ctx.clone();
+    ehttp::fetch(station_vote_request(&uuid), move |response| {
+        let counted = response.and_then(|response| {
+            if !response.ok {
+                return Err(format!("{} {}", response.status, response.status_text));
+            }
+            let reply = parse_vote_reply(response.text().unwrap_or_default())
+                .map_err(|err| err.to_string())?;
+            if reply.ok {
+                Ok(())
+            } else {
+                Err(reply.message)
+            }
+        });
+        if let Err(reason) = counted {
+            rejected_votes.lock().unwrap().push((uuid, reason));
+            ctx.request_repaint();
+        }
+    });
+}
+
+/// Add the change to the votes of the stations with the given UUID.
+fn change_votes<'a>(stations: impl IntoIterator<Item = &'a mut Station>, uuid: &str, change: i32) {
+    for station in stations {
+        if station.stationuuid == uuid {
+            station.votes += change;
+        }
+    }
+}
+
+/// Return the English name of the station field that is searched.
+fn search_by_label(by: SearchBy) -> &'static str {
+    match by {
+        SearchBy::Name => "Name",
+        SearchBy::Tag => "Tag",
+        SearchBy::Country => "Country",
+        SearchBy::Language => "Language",
+        SearchBy::CountryCode => "Country code",
+    }
+}
+
+/// Remove the station from the favorites if it is there. Else add it. Stations
+/// are compared by UUID, so a station is never added twice.
+fn toggle_favorite(favorites: &mut Vec<Station>, station: &Station) {
+    let count = favorites.len();
+    favorites.retain(|favorite| favorite.stationuuid != station.stationuuid);
+    if favorites.len() == count {
+        favorites.push(station.clone());
+    }
+}
+
+/// Move the favorite at the given position so it comes before the favorite
+/// at the other position, or last if that position is past the end. Dropping
+/// a favorite right before or after itself leaves it in place.
+fn move_favorite(favorites: &mut Vec<Station>, from: usize, before: usize) {
+    if from >= favorites.len() || before == from || before == from + 1 {
+        return;
+    }
+    let favorite = favorites.remove(from);
+    // The favorites after the moved one shifted back by one.
+    let to = if before > from { before - 1 } else { before };
+    favorites.insert(to.min(favorites.len()), favorite);
+}
+
+/// Add the stations to the favorites, leaving out the ones already there.
+fn add_favorites<'a>(
+    favorites: &mut Vec<Station>,
+    stations: impl IntoIterator<Item = &'a Station>,
+) {
+    for station in stations {
+        if !favorites
+            .iter()
+            .any(|favorite| favorite.stationuuid == station.stationuuid)
+        {
+            favorites.push(station.clone());
+        }
+    }
+}
+
+/// Move the played station to the front of the history, or add it there if it
+/// is not in the history yet. The oldest stations are forgotten once the
+/// history is full.
+fn add_to_history(history: &mut VecDeque<Station>, station: &Station) {
+    history.retain(|played| played.stationuuid != station.stationuuid);
+    history.push_front(station.clone());
+    history.truncate(HISTORY_LIMIT);
+}
+
+/// Move the searched text to the front of the search history, or add it there
+/// if it is not in the history yet. The oldest searches are forgotten once the
+/// history is full.
+fn add_to_search_history(search_history: &mut VecDeque<String>, text: &str) {
+    search_history.retain(|searched| searched != text);
+    search_history.push_front(text.to_owned());
+    search_history.truncate(SEARCH_HISTORY_LIMIT);
+}
+
+/// Return how well the station matches the preferred country and language.
+/// Empty preferences match no station.
+fn preference_score(station: &Station, country: &str, language: &str) -> u8 {
+    let (country, language) = (country.trim(), language.trim());
+
+    let country_matches = !country.is_empty() && station.country.eq_ignore_ascii_case(country);
+    // A station may list several languages separated by commas.
+    let language_matches = !language.is_empty()
+        && station
+            .language
+            .split(',')
+            .any(|station_language| station_language.trim().eq_ignore_ascii_case(language));
+
+    country_matches as u8 + language_matches as u8
+}
+
+/// Sort the stations so that the ones from the preferred country and language
+/// come first. The sort is stable, so equally preferred stations keep their
+/// order.
+fn rank_by_preference(stations: &mut [Station], country: &str, language: &str) {
+    stations.sort_by_key(|station| std::cmp::Reverse(preference_score(station, country, language)));
+}
+
+/// Remove the stations with the same name and URL, keeping the most voted one
+/// where the first of them was.
+fn dedupe_stations(stations: Vec<Station>) -> Vec<Station> {
+    let mut unique: Vec<Station> = Vec::with_capacity(stations.len());
+    // The position in the unique stations of each name and URL.
+    let mut positions: HashMap<(String, String), usize> = HashMap::new();
+
+    for station in stations {
+        let key = (station.name.clone(), station.stream_url().to_owned());
+        match positions.get(&key) {
+            Some(&position) => {
+                if station.votes > unique[position].votes {
+                    unique[position] = station;
+                }
+            }
+            None => {
+                positions.insert(key, unique.len());
+                unique.push(station);
+            }
+        }
+    }
+    unique
+}
+
+/// Sort the stations in the given order. The sort is stable, so stations that
+/// are equal in that order keep the radio browser order.
+fn sort_stations(stations: &mut [&Station], sort_by: SortBy) {
+    match sort_by {
+        SortBy::Relevance => {}
+        SortBy::Votes => stations.sort_by_key(|station| std::cmp::Reverse(station.votes)),
+        SortBy::Bitrate => stations.sort_by_key(|station| std::cmp::Reverse(station.bitrate)),
+        SortBy::Clicks => stations.sort_by_key(|station| std::cmp::Reverse(station.clickcount)),
+        SortBy::Name => stations.sort_by(|a, b| {
+            let (a, b) = (a.name.trim(), b.name.trim());
+            // Compare the names ignoring case, leaving the empty names last.
+            a.is_empty().cmp(&b.is_empty()).then_with(|| {
+                a.chars()
+                    .flat_map(char::to_lowercase)
+                    .cmp(b.chars().flat_map(char::to_lowercase))
+            })
+        }),
+    }
+}
+
+/// Return the country of the stations if all of them are from the same
+/// country. Return None if there are no stations or their country is unknown.
+fn single_country(stations: &[Station]) -> Option<&str> {
+    let country = stations.first()?.country.as_str();
+    if !country.is_empty() && stations.iter().all(|station| station.country == country) {
+        Some(country)
+    } else {
+        None
+    }
+}
+
+/// Return the sorted known states/regions of the stations, without repetitions.
+fn distinct_states(stations: &[Station]) -> Vec<&str> {
+    let mut states: Vec<&str> = stations
+        .iter()
+        .map(|station| station.state.as_str())
+        .filter(|state| !state.is_empty())
+        .collect();
+    states.sort_unstable();
+    states.dedup();
+    states
+}
+
+/// Send the requests to the radio browser server with the given host, or to a
+/// random server if the host is empty, to spread the load across the servers.
+fn use_server(host: &str) {
+    if host.is_empty() {
+        set_server(
+            SERVERS
+                .choose(&mut rand::thread_rng())
+                .unwrap_or(&SERVERS[0]),
+        );
+    } else {
+        set_server(host);
+    }
+}
+
+/// Return the tags of the station, without the spaces around them.
+fn station_tags(station: &Station) -> impl Iterator<Item = &str> {
+    station.tags.split(',').map(str::trim)
+}
+
+/// Count how many times each value appears, leaving out empty values. The
+/// most common values come first, and values that appear as often are sorted
+/// alphabetically.
+fn facet_counts<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(&'a str, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values.filter(|value| !value.is_empty()) {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+}
+
+/// Return whether the station is from one of the chosen countries, and has one
+/// of the chosen tags. No chosen countries or tags match every station.
+fn matches_facets(
+    station: &Station,
+    countries: &BTreeSet<String>,
+    tags: &BTreeSet<String>,
+) -> bool {
+    (countries.is_empty() || countries.contains(&station.country))
+        && (tags.is_empty() || station_tags(station).any(|tag| tags.contains(tag)))
+}
+
+/// Show a checkbox for each value and how many stations have it. Checking a
+/// value chooses it, and unchecking it forgets it.
+fn facet_ui(ui: &mut egui::Ui, id: &str, counts: &[(&str, usize)], chosen: &mut BTreeSet<String>) {
+    egui::ScrollArea::vertical()
+        .id_source(id)
+        .max_height(150.0)
+        .show(ui, |ui| {
+            for &(value, count) in counts {
+                let mut checked = chosen.contains(value);
+                if ui
+                    .checkbox(&mut checked, format!("{} ({})", value, count))
+                    .changed()
+                {
+                    if checked {
+                        chosen.insert(value.to_owned());
+                    } else {
+                        chosen.remove(value);
+                    }
+                }
+            }
+        });
+}
+
+/// Return whether the station is shown with the chosen filters: the
+/// broadcaster, the state, the country and tag facets, the stations checked
+/// as working since the given time, if any, and the HTTPS streams, if asked
+/// for.
+fn is_shown(
+    station: &Station,
+    broadcaster_filter: &Option<String>,
+    state_filter: &Option<String>,
+    country_facets: &BTreeSet<String>,
+    tag_facets: &BTreeSet<String>,
+    checked_since: Option<i64>,
+    https_only: bool,
+) -> bool {
+    broadcaster_filter
+        .as_ref()
+        .is_none_or(|domain| homepage_domain(&station.homepage).as_ref() == Some(domain))
+        && state_filter
+            .as_ref()
+            .is_none_or(|state| station.state == *state)
+        && matches_facets(station, country_facets, tag_facets)
+        && checked_since.is_none_or(|time| station.checked_ok_since(time))
+        && (!https_only || station.is_https())
+}
+
+/// Show a quick peek of the station details, such as its tags and location.
+fn station_tooltip_ui(ui: &mut egui::Ui, lang: Language, station: &Station) {
+    ui.strong(&station.name);
+
+    // Show the tags as chips.
+    tag_chips(ui, &station.tags);
+
+    // Show the location, leaving out unknown parts.
+    let location: Vec<&str> = [station.state.as_str(), station.country.as_str()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect();
+    if !location.is_empty() {
+        ui.label(format!("📍 {}", location.join(", ")));
+    }
+
+    ui.label(format!("🎵 {} kbps {}", station.bitrate, station.codec));
+    ui.label(format!("👍 {}", tf(lang, "{} votes", &[&station.votes])));
+
+    // Show when the station last passed a check by the radio browser.
+    let last_online = if station.lastcheckoktime.is_empty() {
+        t(lang, "unknown")
+    } else {
+        station.lastcheckoktime.as_str()
+    };
+    ui.label(tf(lang, "Last online: {}", &[&last_online]));
+}
+
+/// Show a colored chip with the quality of the stream and its codec, such as
+/// "HD FLAC". Nothing is shown if neither is known.
+fn quality_badge(ui: &mut egui::Ui, station: &Station) {
+    let codec = station.codec.trim().to_uppercase();
+    let codec = if codec == "UNKNOWN" {
+        ""
+    } else {
+        codec.as_str()
+    };
+    let quality = station.quality();
+
+    let badge = match quality {
+        Some(quality) => egui::RichText::new(format!("{} {}", quality.label(), codec).trim())
+            .color(egui::Color32::WHITE)
+            .background_color(quality_color(quality)),
+        // Show the codec alone, like a tag.
+        None if !codec.is_empty() => {
+            egui::RichText::new(codec).background_color(ui.visuals().faint_bg_color)
+        }
+        None => return,
+    };
+    ui.label(badge.small());
+}
+
+/// Return the background color of the badge of the given quality.
+fn quality_color(quality: Quality) -> egui::Color32 {
+    match quality {
+        Quality::High => egui::Color32::from_rgb(40, 140, 60),
+        Quality::Standard => egui::Color32::from_rgb(50, 100, 170),
+        Quality::Low => egui::Color32::from_rgb(150, 110, 40),
+    }
+}
+
+/// Gives icon buttons a text alternative.
+trait Labeled {
+    /// Describe the widget with the label, both to screen readers and in a
+    /// tooltip, since its icon alone may be unclear.
+    fn labeled(self, label: &str) -> Self;
+}
+
+impl Labeled for egui::Response {
+    fn labeled(self, label: &str) -> Self {
+        self.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, label));
+        self.on_hover_text(label)
+    }
+}
+
+/// Return the text cut to the given number of characters, ending with an
+/// ellipsis, or the text itself if it is not longer. The text is cut between
+/// characters, never inside one. Only a cut text is owned, which tells whether
+/// the text was cut.
+fn truncate_text(text: &str, max_chars: usize) -> std::borrow::Cow<'_, str> {
+    match text.char_indices().nth(max_chars) {
+        Some(_) => {
+            // Leave room for the ellipsis.
+            let end = text
+                .char_indices()
+                .nth(max_chars.saturating_sub(1))
+                .map_or(text.len(), |(index, _)| index);
+            format!("{}…", text[..end].trim_end()).into()
+        }
+        None => text.into(),
+    }
+}
+
+/// Show text that can be selected and copied, like a label. The parts that
+/// match the highlighted text (ignoring case) are shown in bold.
+fn selectable_text(ui: &mut egui::Ui, text: &str, highlight: &str) -> egui::Response {
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let color = ui.visuals().text_color();
+    let strong_color = ui.visuals().strong_text_color();
+
+    // Lay out the text, using the strong color for the matching parts.
+    let mut job = egui::text::LayoutJob::default();
+    for (part, is_match) in split_matches(text, highlight) {
+        job.append(
+            part,
+            0.0,
+            egui::TextFormat::simple(font_id.clone(), if is_match { strong_color } else { color }),
+        );
+    }
+    let mut layouter =
+        |ui: &egui::Ui, _text: &str, _wrap_width: f32| ui.fonts().layout_job(job.clone());
+
+    // Make the text box as wide as the text, so it looks like a label.
+    let width = layouter(ui, text, f32::INFINITY).size().x;
+
+    // A read-only text box allows selecting and copying the text.
+    let mut text = text;
+    ui.add(
+        egui::TextEdit::singleline(&mut text)
+            .frame(false)
+            .margin(egui::Vec2::ZERO)
+            .desired_width(width)
+            .layouter(&mut layouter),
+    )
+}
+
+/// Split the text into the parts that match the query (ignoring case) and the
+/// parts that do not. Each part is returned with whether it matches or not.
+fn split_matches<'a>(text: &'a str, query: &str) -> Vec<(&'a str, bool)> {
+    let query: Vec<char> = query.trim().chars().collect();
+    if query.is_empty() {
+        return vec![(text, false)];
+    }
+
+    // Return the length in bytes of the query at the start of the text, if the
+    // text starts with the query (ignoring case).
+    let match_length = |text: &str| {
+        let mut length = 0;
+        let mut chars = text.chars();
+        for &q in &query {
+            let c = chars.next()?;
+            if c != q && !c.to_lowercase().eq(q.to_lowercase()) {
+                return None;
+            }
+            length += c.len_utf8();
+        }
+        Some(length)
+    };
+
+    let mut parts = Vec::new();
+    // The start of the part that does not match.
+    let mut plain_start = 0;
+    let mut position = 0;
+    while let Some(c) = text[position..].chars().next() {
+        match match_length(&text[position..]) {
+            Some(length) => {
+                if plain_start < position {
+                    parts.push((&text[plain_start..position], false));
+                }
+                parts.push((&text[position..position + length], true));
+                // Continue searching after the match.
+                position += length;
+                plain_start = position;
+            }
+            // Move to the next character.
+            None => position += c.len_utf8(),
+        }
+    }
+    if plain_start < text.len() {
+        parts.push((&text[plain_start..], false));
+    }
+    parts
+}
+
+/// Extract the domain from a station homepage, such as "somafm.com" from
+/// "https://www.somafm.com/dubstep/". Return None if there is no domain.
+fn homepage_domain(homepage: &str) -> Option<String> {
+    // Remove the scheme, if any.
+    let without_scheme = match homepage.trim().split_once("://") {
+        Some((_, rest)) => rest,
+        None => homepage.trim(),
+    };
+    // Keep the host, and drop the path, query, fragment, and port.
+    let host = without_scheme
+        .split(['/', '?', '#', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    // Stations of the same broadcaster may or may not use the www prefix.
+    let domain = host.strip_prefix("www.").unwrap_or(&host);
+
+    if domain.is_empty() {
+        None
+    } else {
+        Some(domain.to_owned())
+    }
+}
+
+/// Implement trait to create default window.
+impl App {
+    /// Create default window, which plays the startup station, if any.
+    pub fn new(cc: &eframe::CreationContext<'_>, startup: Startup) -> Self {
+        // Load the app state saved on the last shutdown, if any.
+        #[cfg(feature = "persistence")]
+        let saved = cc
+            .storage
+            .and_then(|storage| eframe::get_value::<App>(storage, eframe::APP_KEY));
+        #[cfg(feature = "persistence")]
+        let saved = saved.unwrap_or_default();
+        #[cfg(not(feature = "persistence"))]
+        let saved = App::default();
+
+        let mut app = App { startup, ..saved };
+
+        // Start at the default volume, if there is one.
+        if let Some(level) = app.default_volume {
+            app.volume = Volume::new(level);
+        }
+        // Only boost the volume if the user allows it.
+        app.volume.limit(max_volume(app.volume_boost));
+
+        // Apply the saved theme.
+        cc.egui_ctx.set_visuals(theme(app.dark_mode));
+
+        // The media player is not saved, and starts at the default volume, so
+        // pass it the saved volume.
+        app.media_player.set_volume(app.volume.on_slider);
+
+        // The media player is not saved, so the saved station is loaded but
+        // paused, and plays again with the play button.
+        app.transport = if app.station_url.is_empty() {
+            Transport::Stopped
+        } else {
+            Transport::Paused
+        };
+
+        // Send the requests to the chosen server.
+        use_server(&app.server);
+
+        // Fade between stations, and fade them in, if the user chose to.
+        app.media_player.set_crossfade(app.crossfade);
+        app.media_player.set_fade_in(app.fade_in);
+
+        // Ask for the title of the playing track as often as the user chose.
+        app.media_player
+            .set_metadata_interval(app.metadata_interval);
+
+        // Equalize the stations, if the user chose to.
+        #[cfg(not(target_arch = "wasm32"))]
+        app.media_player.set_equalizer(&app.equalizer);
+
+        // Play through the chosen audio device, if any.
+        if !app.audio_device.is_empty() {
+            app.media_player.set_audio_device(&app.audio_device);
+        }
+
+        // Let the media player show the track titles as soon as they change.
+        app.media_player.set_repaint_context(&cc.egui_ctx);
+
+        // Tell the user right away if stations cannot be played, such as
+        // when VLC is not installed.
+        if let Some(reason) = app.media_player.unavailable_reason() {
+            app.notifications.error(reason);
+        }
+        app
+    }
+}
+
+/// Implement trait to create default app state.
+impl Default for App {
+    /// Create default app state.
+    fn default() -> Self {
+        App::with_player(Box::<PlatformPlayer>::default())
+    }
+}
+
+impl App {
+    /// Create default app state, which plays the stations with the given
+    /// media player.
+    fn with_player(media_player: Box<dyn Player>) -> Self {
+        App {
+            // Name the application (the main window).
+            name: "Online Radio".to_owned(),
+            // Initially there are no downloads.
+            download: Arc::new(Mutex::new(Download::None)),
+
+            // Initially the list of stations is empty.
+            stations: Arc::new(Mutex::new(Vec::new())),
+
+            // Initially no station is loaded.
+            station_url: String::new(),
+
+            // Initially there are no favorite stations.
+            favorites: Vec::new(),
+
+            // Initially no favorite is dragged.
+            dragged_favorite: None,
+
+            // Initially no station was played.
+            history: VecDeque::new(),
+
+            // Initially nothing was searched.
+            search_history: VecDeque::new(),
+
+            // Initially no logos are downloaded.
+            favicons: FaviconCache::default(),
+
+            // Initially there is no text to search.
+            text_to_search: "".to_owned(),
+
+            // Search by station name by default.
+            search_by: SearchBy::Name,
+
+            // Search stations of any bitrate and codec by default.
+            min_bitrate: 0,
+            codec_filter: String::new(),
+
+            // Search stations of any tag, country, and language, in the
+            // order of the radio browser, by default.
+            search_tag: String::new(),
+            search_country: String::new(),
+            search_language: String::new(),
+            search_order: SearchOrder::Relevance,
+
+            // Show the stations in the radio browser order by default.
+            sort_by: SortBy::Relevance,
+
+            // Set the initial volume, which is not mute.
+            volume: Volume::default(),
+
+            // Initially the media player has the shown volume.
+            volume_sent_at: 0.0,
+            volume_is_pending: false,
+
+            // Creates a default About window.
+            about_window: AboutWindow::default(),
+
+            // Initially no station details are shown.
+            station_window: StationWindow::default(),
+
+            // Use the given media player, which is Web-sys on the web, and
+            // VLC natively.
+            media_player,
+
+            // Set the playing icon as the default icon.
+            transport: Transport::Stopped,
+
+            // Initially nothing is playing.
+            now_playing: String::new(),
+
+            // Initially no station is playing.
+            playing_station: None,
+
+            // Initially no station was voted for.
+            voted: HashSet::new(),
+
+            // Initially the stations are acted on one at a time.
+            selection_mode: false,
+            checked_stations: HashSet::new(),
+
+            // Initially no vote was rejected.
+            rejected_votes: Arc::new(Mutex::new(Vec::new())),
+
+            // Initially nothing has been announced.
+            announcer: Announcer::default(),
+
+            // The user settings panel should be closed by default.
+            user_settings_is_open: false,
+
+            // The options panel should be closed by default.
+            options_is_open: false,
+
+            // Start with a panel as wide as its widest widgets.
+            settings_panel_width: 200.0,
+
+            // Keep the last volume by default.
+            default_volume: None,
+
+            // Retrieve as many stations as the radio browser suggests.
+            results_limit: 100,
+
+            // Give up on searches after 15 seconds by default.
+            request_timeout: 15,
+
+            // Spread the load across the servers by default.
+            server: String::new(),
+
+            // Initially no server was typed.
+            server_input: String::new(),
+            server_input_is_invalid: false,
+
+            // Play through the default audio device by default.
+            audio_device: String::new(),
+
+            // Cut from one station to the next by default.
+            crossfade: false,
+
+            // Start the stations at the volume by default.
+            fade_in: false,
+
+            // Ask for the title of the playing track every few seconds by
+            // default.
+            metadata_interval: DEFAULT_METADATA_INTERVAL,
+
+            // Keep the volume at most at 100% by default.
+            volume_boost: false,
+
+            // Keep the same volume for every station by default.
+            remember_station_volumes: false,
+            station_volumes: HashMap::new(),
+
+            // Initially the stations have no profiles.
+            station_profiles: HashMap::new(),
+
+            // Initially no station plays, so there are no streams to try.
+            mirrors: Mirrors::default(),
+
+            // Initially the stations play their own streams.
+            preferred_streams: HashMap::new(),
+
+            // Do not equalize the stations by default.
+            #[cfg(not(target_arch = "wasm32"))]
+            equalizer: equalizer::Equalizer::default(),
+
+            // Save the recordings to the music directory by default.
+            #[cfg(not(target_arch = "wasm32"))]
+            recordings_dir: String::new(),
+
+            // Initially nothing is recorded.
+            #[cfg(not(target_arch = "wasm32"))]
+            recorder: recorder::Recorder::default(),
+
+            // Start paused by default.
+            autoplay_on_launch: false,
+
+            // Play the last played station on launch by default.
+            default_station: None,
+
+            // Search with Enter or the search button by default.
+            search_as_you_type: false,
+
+            // Play the stations in the results right away by default.
+            play_on_click: true,
+
+            // Leave room between the stations by default.
+            density: Density::Comfortable,
+
+            // Show every column but the tags.
+            columns: Columns::default(),
+
+            // Initially the search text was not edited.
+            search_edited_at: None,
+
+            // Set the default language to English.
+            language: Language::English,
+
+            // Use the dark theme by default.
+            dark_mode: true,
+
+            // Leave out broken stations by default.
+            hide_broken: true,
+
+            // Show the stations that were not checked recently by default.
+            hide_unreachable: false,
+
+            // Browsers block HTTP streams on secure pages, so only show the
+            // HTTPS streams on the web by default.
+            https_only: cfg!(target_arch = "wasm32"),
+
+            // Initially no station is marked as broken.
+            blocked_stations: BTreeMap::new(),
+
+            // Initially no country or language is preferred.
+            preferred_country: String::new(),
+
+            // The country of the local search is detected when first needed.
+            local_country_code: String::new(),
+            preferred_language: String::new(),
+
+            // Initially stations from all broadcasters are shown.
+            broadcaster_filter: None,
+
+            // Initially stations from all states are shown.
+            state_filter: None,
+
+            // Initially stations from all countries are shown.
+            country_facets: BTreeSet::new(),
+
+            // Initially stations with any tag are shown.
+            tag_facets: BTreeSet::new(),
+
+            // Initially there is nothing to tell the user.
+            notifications: Notifications::default(),
+            reported_playback_error: None,
+
+            // Nothing is played on startup unless asked for.
+            startup: Startup::Nothing,
+
+            // Initially there is no station to play once downloaded.
+            play_first_result: false,
+
+            // Initially nothing has been searched.
+            current_query: None,
+
+            // Initially there are no stations to load.
+            more_results: false,
+
+            // Initially nothing has been searched.
+            searched_text: None,
+
+            // Start with a clean view by default.
+            restore_last_view: false,
+
+            // Initially there is no view to restore.
+            last_view: None,
+
+            // The reset dialog is only shown when asked for.
+            reset_dialog_is_open: false,
+
+            // The favorites and the history are kept by default.
+            reset_clears_saved: false,
+
+            // The play URL dialog is only shown when asked for.
+            play_url_dialog_is_open: false,
+
+            // Initially no URL was typed.
+            url_to_play: String::new(),
+            url_is_invalid: false,
+
+            // The user has not chosen to quit.
+            quit_requested: false,
+
+            // Initially the station plays on after the track.
+            stop_after_track: None,
+        }
+    }
+}
+
+impl App {
+    /// Restore every setting to its default value. The stations, the search,
+    /// the playing station, and the open windows are kept, and so are the
+    /// favorites and the history, unless the user chose to clear them too.
+    fn reset_settings(&mut self) {
+        // The defaults are set one by one, rather than taken from a new app,
+        // which would start another media player.
+        self.min_bitrate = 0;
+        self.codec_filter = String::new();
+        self.search_order = SearchOrder::Relevance;
+        self.sort_by = SortBy::Relevance;
+        self.volume = Volume::default();
+        self.volume_sent_at = 0.0;
+        self.volume_is_pending = false;
+        self.settings_panel_width = 200.0;
+        self.default_volume = None;
+        self.results_limit = 100;
+        self.request_timeout = 15;
+        self.server = String::new();
+        self.server_input = String::new();
+        self.server_input_is_invalid = false;
+        self.audio_device = String::new();
+        self.crossfade = false;
+        self.fade_in = false;
+        self.metadata_interval = DEFAULT_METADATA_INTERVAL;
+        self.volume_boost = false;
+        self.remember_station_volumes = false;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.equalizer = equalizer::Equalizer::default();
+            self.recordings_dir = String::new();
+        }
+        self.autoplay_on_launch = false;
+        self.default_station = None;
+        self.search_as_you_type = false;
+        self.play_on_click = true;
+        self.density = Density::Comfortable;
+        self.columns = Columns::default();
+        self.language = Language::English;
+        self.dark_mode = true;
+        self.hide_broken = true;
+        self.hide_unreachable = false;
+        self.https_only = cfg!(target_arch = "wasm32");
+        self.preferred_country = String::new();
+        self.local_country_code = String::new();
+        self.preferred_language = String::new();
+        self.restore_last_view = false;
+        self.last_view = None;
+        self.reset_dialog_is_open = false;
+
+        // Keep the favorites and the history, which are not settings, unless
+        // the user chose to clear them too.
+        if std::mem::take(&mut self.reset_clears_saved) {
+            self.favorites = Vec::new();
+            self.history = VecDeque::new();
+            self.search_history = VecDeque::new();
+        }
+
+        // Pass the restored settings to the media player, and to the radio
+        // browser. The theme is passed to egui once the panels are drawn.
+        self.media_player.set_volume(self.volume.on_slider);
+        self.media_player.set_audio_device(&self.audio_device);
+        self.media_player.set_crossfade(self.crossfade);
+        self.media_player.set_fade_in(self.fade_in);
+        self.media_player
+            .set_metadata_interval(self.metadata_interval);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.media_player.set_equalizer(&self.equalizer);
+        use_server(&self.server);
+    }
+}
+
+/// Define function for running app natively and on web.
+impl eframe::App for App {
+    /// Called by the frame work to save state before shutdown.
+    /// Note that you must enable the `persistence` feature for this to work.
+    #[cfg(feature = "persistence")]
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Take a snapshot of the current view, if it should be restored.
+        self.last_view = if self.restore_last_view {
+            Some(ViewState {
+                query: self.searched_text.clone(),
+                retrieved: self
+                    .current_query
+                    .as_ref()
+                    .map_or(0, |query| query.offset + query.page_size()),
+                broadcaster_filter: self.broadcaster_filter.clone(),
+                state_filter: self.state_filter.clone(),
+            })
+        } else {
+            None
+        };
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+
+    /// Called once on shutdown, after saving. The app may not be dropped
+    /// when the window closes, so stop the station here, or VLC would keep
+    /// playing.
+    fn on_exit(&mut self, _gl: &eframe::glow::Context) {
+        self.media_player.shutdown();
+    }
+
+    /// Called each time the UI needs repainting, which may be many times per
+    /// second.  Put your widgets into a `SidePanel`, `TopPanel`,
+    /// `CentralPanel`, `Window` or `Area`.
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.ui(ctx);
+        // Keep a copy of the state, which is saved if the app panics.
+        #[cfg(all(feature = "persistence", not(target_arch = "wasm32")))]
+        crash_save::keep_snapshot(self);
+        if std::mem::take(&mut self.quit_requested) {
+            frame.quit();
+        }
+    }
+}
+
+impl App {
+    /// Show the user interface, and act on what the user did during this
+    /// frame. Quitting is left to update(), which has the frame.
+    fn ui(&mut self, ctx: &egui::Context) {
+        let Self {
+            name,
+            download,
+            stations,
+            station_url,
+            favorites,
+            dragged_favorite,
+            history,
+            search_history,
+            favicons,
+            text_to_search,
+            search_by,
+            min_bitrate,
+            codec_filter,
+            search_tag,
+            search_country,
+            search_language,
+            search_order,
+            sort_by,
+            volume,
+            volume_sent_at,
+            volume_is_pending,
+            about_window,
+            station_window,
+            media_player,
+            transport,
+            now_playing,
+            playing_station,
+            voted,
+            selection_mode,
+            checked_stations,
+            rejected_votes,
+            announcer,
+            user_settings_is_open,
+            options_is_open,
+            settings_panel_width,
+            default_volume,
+            results_limit,
+            request_timeout,
+            server,
+            server_input,
+            server_input_is_invalid,
+            audio_device,
+            crossfade,
+            fade_in,
+            metadata_interval,
+            volume_boost,
+            remember_station_volumes,
+            station_volumes,
+            station_profiles,
+            mirrors,
+            preferred_streams,
+            #[cfg(not(target_arch = "wasm32"))]
+            equalizer,
+            #[cfg(not(target_arch = "wasm32"))]
+            recordings_dir,
+            #[cfg(not(target_arch = "wasm32"))]
+            recorder,
+            autoplay_on_launch,
+            default_station,
+            search_as_you_type,
+            play_on_click,
+            density,
+            columns,
+            search_edited_at,
+            language,
+            dark_mode,
+            hide_broken,
+            hide_unreachable,
+            https_only,
+            blocked_stations,
+            preferred_country,
+            preferred_language,
+            local_country_code,
+            broadcaster_filter,
+            state_filter,
+            country_facets,
+            tag_facets,
+            notifications,
+            reported_playback_error,
+            startup,
+            play_first_result,
+            current_query,
+            more_results,
+            searched_text,
+            restore_last_view,
+            last_view,
+            reset_dialog_is_open,
+            reset_clears_saved,
+            play_url_dialog_is_open,
+            url_to_play,
+            url_is_invalid,
+            quit_requested,
+            stop_after_track,
+        } = self;
+
+        // The language of this frame, used to translate the text.
+        let lang = *language;
+
+        // The profile of the playing station, if it has one.
+        let mut playing_profile = profile_of(station_profiles, playing_station.as_ref()).cloned();
+
+        // Why stations cannot be played, if they cannot, such as when VLC is
+        // not installed. The play buttons are disabled then.
+        let unavailable_reason = media_player.unavailable_reason();
+        let can_play = unavailable_reason.is_none();
+
+        // Restore the last view on the first frame, unless the application
+        // was started with a station to play.
+        if let Some(view) = last_view.take() {
+            if *restore_last_view && matches!(startup, Startup::Nothing) {
+                // Search again to repopulate the stations, retrieving as
+                // many stations as were shown, so the next pages that were
+                // loaded are shown too. This runs on the first frame, once
+                // the context that repaints on the response exists.
+                if let Some(query_text) = view.query.filter(|text| !text.trim().is_empty()) {
+                    let mut query = SearchQuery::new(*search_by, &query_text);
+                    query.hide_broken = *hide_broken;
+                    query.limit = view.retrieved.max(*results_limit);
+                    query.min_bitrate = *min_bitrate;
+                    query.codec = codec_filter.clone();
+                    query.filters = vec![
+                        (SearchBy::Tag, search_tag.clone()),
+                        (SearchBy::Country, search_country.clone()),
+                        (SearchBy::Language, search_language.clone()),
+                    ];
+                    query.order = *search_order;
+                    fetch_stations(
+                        ctx,
+                        download,
+                        notifications,
+                        lang,
+                        build_search_request(&query),
+                        Fetch::Replace,
+                    );
+                    *current_query = Some(query);
+                    *text_to_search = query_text.clone();
+                    *searched_text = Some(query_text);
+                }
+                *broadcaster_filter = view.broadcaster_filter;
+                *state_filter = view.state_filter;
+            }
+        }
+
+        // Let the media player connect a dropped station again before its
+        // state is shown.
+        media_player.tick();
+
+        // When the playing stream fails, try the other streams of the
+        // station before giving up, such as at another bitrate or on another
+        // server. The station is not paused while its streams are listed.
+        let mut failover_pending = false;
+        if *transport == Transport::Playing
+            && matches!(media_player.state(), PlaybackState::Error(_))
+        {
+            let uuid = playing_station
+                .as_ref()
+                .map(|station| station.stationuuid.as_str());
+            match mirrors.failover(ctx, station_url, uuid) {
+                Failover::Retry(url) => {
+                    log::info!("Trying {} instead of {}", url, station_url);
+                    *station_url = url;
+                    play_url(media_player.as_mut(), station_url);
+                }
+                Failover::Waiting => failover_pending = true,
+                Failover::GiveUp => {}
+            }
+        }
+
+        // Prefer the stream that plays, so the station starts with it the
+        // next time.
+        if media_player.state() == PlaybackState::Playing {
+            if let Some(station) = playing_station {
+                prefer_stream(preferred_streams, station, station_url);
+            }
+        }
+
+        // Whether the user confirmed resetting the settings during this frame.
+        let mut reset_confirmed = false;
+
+        // The station the user chose to play during this frame, if any.
+        let mut station_to_play: Option<Station> = None;
+
+        // The station the user chose to play later with the play/pause
+        // button during this frame, if any.
+        let mut station_to_select: Option<Station> = None;
+
+        // Whether the user stopped the playing station during this frame.
+        let mut stop_requested = false;
+
+        // The UUID of the station the user voted for during this frame, if
+        // any.
+        let mut station_to_vote: Option<String> = None;
+
+        // Whether the user asked for the next page of the search during this
+        // frame.
+        let mut load_more = false;
+
+        // Whether the user asked to retry the failed download during this
+        // frame.
+        let mut retry_download = false;
+
+        // Start playing the startup station on the first frame.
+        match std::mem::take(startup) {
+            // Play the station chosen in the options, if any.
+            Startup::Nothing => {
+                if *autoplay_on_launch {
+                    station_to_play = default_station.clone().or_else(|| history.front().cloned());
+                }
+            }
+            Startup::Url(url) => {
+                *station_url = url;
+                play_url(media_player.as_mut(), station_url);
+                *transport = Transport::Playing;
+                // The station name is unknown, so show the URL.
+                *now_playing = station_url.clone();
+                *playing_station = None;
+            }
+            // A shared link may hold anything, so check the UUID first.
+            Startup::Station(uuid) if !is_station_uuid(&uuid) => {
+                notifications.error(t(lang, "The shared station link is invalid."));
+            }
+            Startup::Station(uuid) => {
+                // Retrieve the station, and play it when done.
+                fetch_stations(
+                    ctx,
+                    download,
+                    notifications,
+                    lang,
+                    station_by_uuid_request(&uuid),
+                    Fetch::Replace,
+                );
+                // A single station has no more pages.
+                *current_query = None;
+                *play_first_result = true;
+            }
+        }
+
+        // Show the about window when the menu item is pressed.
+        about_window.show(ctx, lang);
+
+        // Show the details of the chosen station, if any. A change to the
+        // profile of the playing station is heard right away.
+        if let Some(uuid) = station_window.show(ctx, lang, station_profiles) {
+            if playing_station
+                .as_ref()
+                .is_some_and(|station| station.stationuuid == uuid)
+            {
+                let profile = station_profiles.get(&uuid).cloned();
+                switch_profile(
+                    media_player.as_mut(),
+                    volume,
+                    max_volume(*volume_boost),
+                    #[cfg(not(target_arch = "wasm32"))]
+                    equalizer,
+                    playing_profile.as_ref(),
+                    profile.as_ref(),
+                    false,
+                );
+                playing_profile = profile;
+            }
+        }
+
+        // Examples of how to create different panels and windows.
+        // Pick whichever suits you.
+        // Tip: a good default choice is to just keep the `CentralPanel`.
+        // For inspiration and more examples, go to https://emilk.github.io/egui
+
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            // The top panel is often a good place for a menu bar:
+            egui::menu::bar(ui, |ui| {
+                // Add theme switch in menu bar, and remember the chosen
+                // theme.
+                egui::global_dark_light_mode_switch(ui);
+                *dark_mode = ui.visuals().dark_mode;
+                // Add a menu bar category for the current file/page.
+                ui.menu_button(t(lang, "File"), |ui| {
+                    // Add a menu item for playing a stream that is not in the
+                    // database.
+                    if ui.button(t(lang, "Play URL…")).clicked() {
+                        *play_url_dialog_is_open = true;
+                        ui.close_menu();
+                    }
+                    // Add a menu item for quitting the application.
+                    if ui.button(t(lang, "Quit")).clicked() {
+                        *quit_requested = true;
+                    }
+                });
+
+                // Add a menu bar category for showing iformation about the app.
+                ui.menu_button(t(lang, "Help"), |ui| {
+                    // Add a menu item for shoowing the information.
+                    if ui.button(t(lang, "About")).clicked() {
+                        // Toggle the window on and off.
+                        self.about_window.is_open = !self.about_window.is_open;
+                    }
+                });
+            });
+        });
+
+        // Add a search panel to look for station names and access user
+        // settings.
+        egui::TopBottomPanel::top("search_panel").show(ctx, |ui| {
+            // All the widgets should be placed in the same horizontal line, as
+            // opposed to one below the other.
+            ui.horizontal(|ui| {
+                // Show the website name.
+                ui.heading(name.to_string());
+
+                // Create a flag that triggers the download of station from the
+                // database.
+                let mut trigger_fetch = false;
+
+                // Empty text would retrieve a slice of the whole directory,
+                // which is slow and rarely wanted, so it is not searched.
+                let has_query = !text_to_search.trim().is_empty();
+
+                // Add magnifying glass that triggers radio station search.
+                trigger_fetch |= ui
+                    .add_enabled(has_query, egui::Button::new("🔍"))
+                    .labeled(t(lang, "Search"))
+                    .on_disabled_hover_text(t(lang, "Type something to search"))
+                    .clicked();
+
+                // Add a die that plays a random station from the results,
+                // other than the playing one.
+                {
+                    let stations = stations.lock().unwrap();
+                    let candidates: Vec<&Station> = stations
+                        .iter()
+                        .filter(|station| {
+                            station.stream_url() != station_url.as_str()
+                                && !blocked_stations.contains_key(&station.stationuuid)
+                        })
+                        .collect();
+                    if ui
+                        .add_enabled(!candidates.is_empty(), egui::Button::new("🎲"))
+                        .labeled(t(lang, "Play a random station"))
+                        .clicked()
+                    {
+                        station_to_play = candidates
+                            .choose(&mut rand::thread_rng())
+                            .map(|&station| station.clone());
+                    }
+                }
+
+                // Add a button that finds the stations of the country of the
+                // user. The country is detected once, and entered by the user
+                // if it cannot be detected.
+                if ui
+                    .button("📍")
+                    .labeled(t(lang, "Stations near me"))
+                    .clicked()
+                {
+                    if local_country_code.trim().is_empty() {
+                        *local_country_code = detect_country_code().unwrap_or_default();
+                    }
+                    if local_country_code.trim().is_empty() {
+                        notifications.error(t(
+                            lang,
+                            "Could not detect your country. Enter its code in the user settings.",
+                        ));
+                        *user_settings_is_open = true;
+                    } else {
+                        let mut query =
+                            SearchQuery::new(SearchBy::CountryCode, local_country_code.trim());
+                        query.hide_broken = *hide_broken;
+                        query.limit = *results_limit;
+                        fetch_stations(
+                            ctx,
+                            download,
+                            notifications,
+                            lang,
+                            build_search_request(&query),
+                            Fetch::Replace,
+                        );
+                        *current_query = Some(query);
+                        // The searched text is forgotten, since restoring
+                        // the view searches it by the chosen field.
+                        *searched_text = None;
+
+                        // The states, countries, and tags of the previous
+                        // results may not exist in the new results.
+                        *state_filter = None;
+                        country_facets.clear();
+                        tag_facets.clear();
+                    }
+                }
+
+                // Calculate the button width. This will be used for spacing.
+                let button_width = ui.spacing().interact_size.x;
+
+                // Let the user choose the station field to search, such as
+                // the name or a tag like "jazz".
+                egui::ComboBox::from_id_source("search_by")
+                    .selected_text(t(lang, search_by_label(*search_by)))
+                    .width(button_width * 2.0)
+                    .show_ui(ui, |ui| {
+                        for by in SearchBy::ALL {
+                            ui.selectable_value(search_by, by, t(lang, search_by_label(by)));
+                        }
+                    });
+
+                // Calculate the available width. This will be used for spacing.
+                let width = ui.available_width();
+                // Add a search bar to search for stations. Adjust search bar
+                // width based on available wdith.
+                let search = ui.add(
+                    egui::TextEdit::singleline(text_to_search)
+                        .desired_width(width - button_width * 3.2)
+                        .hint_text(t(lang, "Search…")),
+                );
+
+                // The search bar triggers a radio station search whenever the
+                // user presses "Enter".
+                trigger_fetch |= search.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+
+                // Show the recent searches below the search bar when it gets
+                // the focus, so they can be searched again with a click.
+                let search_history_id = ui.make_persistent_id("search_history");
+                if search.gained_focus() && !search_history.is_empty() {
+                    ui.memory().open_popup(search_history_id);
+                }
+                let mut clear_search_history = false;
+                egui::popup_below_widget(ui, search_history_id, &search, |ui| {
+                    for searched in search_history.iter() {
+                        if ui.selectable_label(false, searched).clicked() {
+                            *text_to_search = searched.clone();
+                            trigger_fetch = true;
+                        }
+                    }
+                    ui.separator();
+                    clear_search_history = ui.button(t(lang, "Clear history")).clicked();
+                });
+                if clear_search_history {
+                    search_history.clear();
+                }
+                if trigger_fetch {
+                    ui.memory().close_popup();
+                }
+
+                // When searching as the user types, search once the user
+                // stops typing for a moment, so each key does not send a
+                // request.
+                let now = ui.input().time;
+                if *search_as_you_type && search.changed() {
+                    *search_edited_at = Some(now);
+                }
+                if let Some(edited_at) = *search_edited_at {
+                    if now - edited_at >= SEARCH_DELAY {
+                        *search_edited_at = None;
+                        trigger_fetch = true;
+                    } else {
+                        // Keep repainting until the delay is over.
+                        ui.ctx().request_repaint();
+                    }
+                }
+
+                // Add a button that searches the last search again from its
+                // first page, such as to see new stations or their latest
+                // checks. There is nothing to refresh before searching.
+                if ui
+                    .add_enabled(current_query.is_some(), egui::Button::new("🔄"))
+                    .labeled(t(lang, "Refresh results"))
+                    .clicked()
+                {
+                    if let Some(query) = current_query {
+                        query.offset = 0;
+                        fetch_stations(
+                            ctx,
+                            download,
+                            notifications,
+                            lang,
+                            build_search_request(query),
+                            Fetch::Refresh,
+                        );
+                    }
+                }
+
+                // Add a button that empties the station list, so the user
+                // can return to a clean state without searching again.
+                // There is nothing to clear when the list is empty.
+                let has_stations = !stations.lock().unwrap().is_empty();
+                if ui
+                    .add_enabled(has_stations, egui::Button::new("✖"))
+                    .labeled(t(lang, "Clear results"))
+                    .clicked()
+                {
+                    stations.lock().unwrap().clear();
+                    // Forget the search, so no next page is loaded.
+                    *current_query = None;
+                    *more_results = false;
+                    *state_filter = None;
+                    country_facets.clear();
+                    tag_facets.clear();
+                }
+
+                if trigger_fetch && !text_to_search.trim().is_empty() {
+                    // The edited text is searched now.
+                    *search_edited_at = None;
+
+                    // Search stations by the chosen field.
+                    let mut query = SearchQuery::new(*search_by, text_to_search);
+                    query.hide_broken = *hide_broken;
+                    query.limit = *results_limit;
+                    query.min_bitrate = *min_bitrate;
+                    query.codec = codec_filter.clone();
+                    query.filters = vec![
+                        (SearchBy::Tag, search_tag.clone()),
+                        (SearchBy::Country, search_country.clone()),
+                        (SearchBy::Language, search_language.clone()),
+                    ];
+                    query.order = *search_order;
+                    fetch_stations(
+                        ctx,
+                        download,
+                        notifications,
+                        lang,
+                        build_search_request(&query),
+                        Fetch::Replace,
+                    );
+
+                    // Keep the search, so its next pages can be loaded.
+                    *current_query = Some(query);
+
+                    // Remember the search, so the view can be restored, and
+                    // so it can be searched again from the history.
+                    *searched_text = Some(text_to_search.to_string());
+                    add_to_search_history(search_history, text_to_search.trim());
+
+                    // The states, countries, and tags of the previous
+                    // results may not exist in the new results.
+                    *state_filter = None;
+                    country_facets.clear();
+                    tag_facets.clear();
+                }
+
+                // Add a login button.
+                if ui.button("👤").labeled(t(lang, "User settings")).clicked() {
+                    // This flag is used inside the central panel to draw the
+                    // side panel. The side panel must be drawn inside the
+                    // central body, or it will interfere with the top and
+                    // bottom panels.
+                    *user_settings_is_open = !*user_settings_is_open;
+                }
+
+                // Ctrl+O (Cmd+O on Mac) also toggles the options, unless
+                // the user is typing, such as in the search bar. egui has no
+                // comma key, so the usual Ctrl+, cannot be detected.
+                let options_key_pressed = !ui.ctx().wants_keyboard_input()
+                    && ui.input().modifiers.command
+                    && ui.input().key_pressed(egui::Key::O);
+
+                // Add an options button. Like the user settings, the options
+                // panel is drawn inside the central panel.
+                if ui
+                    .button("☰")
+                    .labeled(t(lang, "Options (Ctrl+O)"))
+                    .clicked()
+                    || options_key_pressed
+                {
+                    *options_is_open = !*options_is_open;
+                }
+            });
+
+            // Let the user combine the search with other fields, narrow it by
+            // the stream quality, and order it. The radio browser filters
+            // and orders the stations, so the next search applies them.
+            egui::CollapsingHeader::new(t(lang, "Advanced search"))
+                .id_source("advanced_search")
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        for (by, text) in [
+                            (SearchBy::Tag, &mut *search_tag),
+                            (SearchBy::Country, &mut *search_country),
+                            (SearchBy::Language, &mut *search_language),
+                        ] {
+                            // The searched field is matched with the search
+                            // bar instead.
+                            ui.add_enabled(
+                                by != *search_by,
+                                egui::TextEdit::singleline(text)
+                                    .desired_width(100.0)
+                                    .hint_text(t(lang, search_by_label(by))),
+                            );
+                        }
+                        ui.label(t(lang, "Order: "));
+                        egui::ComboBox::from_id_source("search_order")
+                            .selected_text(t(lang, search_order.label()))
+                            .show_ui(ui, |ui| {
+                                for order in SearchOrder::ALL {
+                                    ui.selectable_value(
+                                        search_order,
+                                        order,
+                                        t(lang, order.label()),
+                                    );
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(t(lang, "Minimum bitrate: "));
+                        ui.add(
+                            egui::DragValue::new(min_bitrate)
+                                .clamp_range(0..=320)
+                                .speed(8.0)
+                                .suffix(" kbps"),
+                        );
+                        ui.label(t(lang, "Codec: "));
+                        egui::ComboBox::from_id_source("codec_filter")
+                            .selected_text(if codec_filter.is_empty() {
+                                t(lang, "Any")
+                            } else {
+                                codec_filter.as_str()
+                            })
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(codec_filter, String::new(), t(lang, "Any"));
+                                for codec in CODECS {
+                                    ui.selectable_value(codec_filter, codec.to_owned(), codec);
+                                }
+                            });
+                    });
+                });
+        });
+
+        // Show the playing station, and the playing track if the station
+        // sends it.
+        let now_playing_text = match media_player.track_title() {
+            Some(title) if !now_playing.is_empty() => format!("{} — {}", now_playing, title),
+            Some(title) => title,
+            None => now_playing.clone(),
+        };
+
+        // Create a bottom pannel. The top/bottom/side panels must be drawn
+        // before the central panel.
+        egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
+            // Display the loaded station and track. Without a station name,
+            // show the loaded URL, if any.
+            if !now_playing_text.is_empty() {
+                selectable_text(ui, &now_playing_text, "");
+            } else if !station_url.is_empty() {
+                selectable_text(ui, station_url, "");
+            } else {
+                ui.label(t(lang, "No station loaded"));
+            }
+
+            // Separate the artist and song names from the buttons.
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                // The keyboard shortcuts do nothing while the user is typing,
+                // such as in the search bar.
+                let shortcuts_enabled = !ui.ctx().wants_keyboard_input();
+
+                // Space also toggles play/pause, unless a widget has the
+                // focus, since Space clicks the focused button.
+                let play_key_pressed = shortcuts_enabled
+                    && ui.memory().focus().is_none()
+                    && ui.input().key_pressed(egui::Key::Space);
+
+                // Toggle play/pause when the play/pause icon is clicked. There
+                // is nothing to play once the station is stopped, or if the
+                // media player cannot play.
+                let play_clicked = ui
+                    .add_enabled(
+                        can_play && !station_url.is_empty(),
+                        egui::Button::new(transport.icon().to_string()),
+                    )
+                    .labeled(t(lang, transport.action()))
+                    .on_disabled_hover_text(unavailable_reason.as_deref().unwrap_or_default())
+                    .clicked();
+                if (play_clicked || play_key_pressed) && can_play && !station_url.is_empty() {
+                    *transport = transport.toggle(media_player.as_mut(), station_url);
+                }
+
+                // Add a button that stops the station.
+                stop_requested |= ui
+                    .add_enabled(!station_url.is_empty(), egui::Button::new("⏹"))
+                    .labeled(t(lang, "Stop"))
+                    .clicked();
+
+                // Let the user pause the station once the playing track
+                // ends, which is only known if the station sends the titles.
+                stop_after_song(media_player.as_mut(), transport, stop_after_track);
+                let track_title = media_player.track_title();
+                if ui
+                    .add_enabled(
+                        track_title.is_some() && *transport == Transport::Playing,
+                        egui::SelectableLabel::new(stop_after_track.is_some(), "🔚"),
+                    )
+                    .labeled(t(lang, "Stop after this song"))
+                    .on_disabled_hover_text(t(
+                        lang,
+                        "The station does not send the titles of the songs",
+                    ))
+                    .clicked()
+                {
+                    *stop_after_track = match stop_after_track {
+                        Some(_) => None,
+                        None => track_title,
+                    };
+                }
+
+                // Show that the station is connecting, so a slow station does
+                // not look broken, or why it could not be played.
+                match media_player.state() {
+                    PlaybackState::Idle | PlaybackState::Playing => {}
+                    PlaybackState::Connecting => {
+                        ui.add(egui::Spinner::new());
+                        ui.label(t(lang, "Connecting…"));
+                        // Keep repainting until the station plays.
+                        ui.ctx().request_repaint();
+                    }
+                    PlaybackState::Reconnecting => {
+                        ui.add(egui::Spinner::new());
+                        ui.label(t(lang, "Reconnecting…"));
+                        // Keep repainting until the station plays again.
+                        ui.ctx().request_repaint();
+                    }
+                    PlaybackState::Error(message) => {
+                        ui.colored_label(egui::Color32::RED, message);
+                    }
+                }
+
+                // Let the user switch between the streams of the station,
+                // such as at another bitrate, without searching again. The
+                // volume is kept, and a paused station stays paused.
+                if mirrors.streams().len() > 1 {
+                    let mut chosen_url = None;
+                    let active = mirrors
+                        .stream(station_url)
+                        .map_or_else(|| t(lang, "Other stream").to_owned(), stream_label);
+                    egui::ComboBox::from_id_source("stream_quality")
+                        .selected_text(active)
+                        .show_ui(ui, |ui| {
+                            for stream in mirrors.streams() {
+                                let url = stream.stream_url();
+                                if ui
+                                    .selectable_label(url == station_url, stream_label(stream))
+                                    .on_hover_text(url)
+                                    .clicked()
+                                {
+                                    chosen_url = Some(url.to_owned());
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text(t(lang, "Stream quality"));
+                    if let Some(url) = chosen_url.filter(|url| url != station_url) {
+                        log::info!("Switching from {} to {}", station_url, url);
+                        *station_url = url;
+                        if *transport == Transport::Playing {
+                            play_url(media_player.as_mut(), station_url);
+                        }
+                    }
+                }
+
+                // Show the measured bitrate next to the one the playing
+                // stream advertises, if known.
+                if let Some(measured) = media_player.measured_bitrate() {
+                    let nominal = mirrors
+                        .stream(station_url)
+                        .or(playing_station.as_ref())
+                        .map_or(0, |station| station.bitrate);
+                    if nominal > 0 {
+                        ui.label(format!("{} / {} kbps", measured, nominal))
+                            .on_hover_text(t(lang, "Measured / advertised bitrate"));
+                    } else {
+                        ui.label(format!("{} kbps", measured))
+                            .on_hover_text(t(lang, "Measured bitrate"));
+                    }
+                }
+
+                // The station may stop on its own, such as when it cannot be
+                // played, so show the play button again.
+                if *transport == Transport::Playing
+                    && !media_player.is_playing()
+                    && !failover_pending
+                {
+                    log::info!("Stopped playing {}", station_url);
+                    *transport = Transport::Paused;
+                }
+
+                // The M key also toggles the volume.
+                let mute_key_pressed = shortcuts_enabled && ui.input().key_pressed(egui::Key::M);
+
+                // Toggle volume on and off when volume icon is clicked.
+                let mute_label = if volume.is_muted {
+                    t(lang, "Unmute")
+                } else {
+                    t(lang, "Mute")
+                };
+                // Whether the user changed the volume during this frame.
+                let mut volume_changed = false;
+                if ui.button(volume.icon()).labeled(mute_label).clicked() || mute_key_pressed {
+                    // Mute or unmute the volume.
+                    volume.toggle_mute();
+                    media_player.set_volume(profile_volume(
+                        volume.on_slider,
+                        playing_profile.as_ref(),
+                        max_volume(*volume_boost),
+                    ));
+                    volume_changed = true;
+                }
+
+                // Display a volume slider, and change the volume when the
+                // slider is clicked or dragged.
+                let previous_volume = volume.on_slider;
+                let max = max_volume(*volume_boost);
+                let slider =
+                    ui.add(egui::Slider::new(&mut volume.on_slider, 0..=max).show_value(false));
+                if slider.drag_started() {
+                    volume.drag_started(previous_volume);
+                }
+                if slider.changed() {
+                    // Reaching 0 mutes the volume, and leaving 0 unmutes it.
+                    volume.slider_moved(previous_volume);
+                    *volume_is_pending = true;
+                    volume_changed = true;
+                }
+                if slider.drag_released() {
+                    volume.drag_stopped();
+                }
+
+                // Show the volume level, and let the user type or drag it to
+                // an exact level. It changes the mute state like the slider.
+                let previous_volume = volume.on_slider;
+                let drag_value = ui.add(
+                    egui::DragValue::new(&mut volume.on_slider)
+                        .clamp_range(0..=max)
+                        .suffix("%"),
+                );
+                if drag_value.changed() {
+                    volume.slider_moved(previous_volume);
+                    *volume_is_pending = true;
+                    volume_changed = true;
+                }
+
+                // While dragging, pass the volume to the media player a few
+                // times per second, rather than on every frame. The last
+                // level is always passed, once the drag ends or the interval
+                // passes.
+                if *volume_is_pending {
+                    let now = ui.input().time;
+                    let is_dragging = slider.dragged() || drag_value.dragged();
+                    if !is_dragging || now - *volume_sent_at >= VOLUME_UPDATE_INTERVAL {
+                        media_player.set_volume(profile_volume(
+                            volume.on_slider,
+                            playing_profile.as_ref(),
+                            max,
+                        ));
+                        *volume_sent_at = now;
+                        *volume_is_pending = false;
+                    } else {
+                        // Keep repainting until the volume is passed.
+                        ui.ctx().request_repaint();
+                    }
+                }
+
+                // The up and down arrows raise and lower the volume by 5.
+                let volume_step = if !shortcuts_enabled {
+                    0
+                } else if ui.input().key_pressed(egui::Key::ArrowUp) {
+                    5
+                } else if ui.input().key_pressed(egui::Key::ArrowDown) {
+                    -5
+                } else {
+                    0
+                };
+                if volume_step != 0 {
+                    volume.change_by(volume_step, max);
+                    media_player.set_volume(profile_volume(
+                        volume.on_slider,
+                        playing_profile.as_ref(),
+                        max,
+                    ));
+                    volume_changed = true;
+                }
+
+                // Remember the volume the user chose for the loaded station,
+                // if asked to. Muting is not remembered, so the station is
+                // not silent the next time.
+                if *remember_station_volumes && volume_changed && !volume.is_muted {
+                    if let Some(station) = playing_station {
+                        station_volumes.insert(station.stationuuid.clone(), volume.on_slider);
+                    }
+                }
+
+                // Copy the playing track to share it. Without a track title,
+                // copy the station name and URL, so the station can be found.
+                if ui
+                    .add_enabled(!station_url.is_empty(), egui::Button::new("📋"))
+                    .labeled(t(lang, "Copy the playing track"))
+                    .clicked()
+                {
+                    ui.output().copied_text = if media_player.track_title().is_some() {
+                        now_playing_text.clone()
+                    } else if now_playing.is_empty() {
+                        station_url.clone()
+                    } else {
+                        format!("{} {}", now_playing, station_url)
+                    };
+                }
+
+                // Record the playing station, or stop recording it. Only
+                // stations from the radio browser are recorded, since the
+                // file is named after the station.
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let is_recording = recorder.url().is_some();
+                    let record_button = ui
+                        .add_enabled(
+                            is_recording
+                                || (playing_station.is_some() && *transport == Transport::Playing),
+                            egui::SelectableLabel::new(is_recording, "⏺"),
+                        )
+                        .labeled(t(lang, "Record the playing station"));
+                    if record_button.clicked() {
+                        if is_recording {
+                            recorder.stop();
+                        } else if let Some(station) = playing_station {
+                            let directory = if recordings_dir.trim().is_empty() {
+                                recorder::default_directory()
+                            } else {
+                                Some(std::path::PathBuf::from(recordings_dir.trim()))
+                            };
+                            match directory {
+                                Some(directory) => {
+                                    if let Err(err) =
+                                        recorder.start(station, &directory, unix_time())
+                                    {
+                                        notifications.error(tf(
+                                            lang,
+                                            "Could not record to {}: {}.",
+                                            &[&directory.display(), &err],
+                                        ));
+                                    }
+                                }
+                                None => notifications.error(t(
+                                    lang,
+                                    "Choose the recordings directory in the options.",
+                                )),
+                            }
+                        }
+                    }
+
+                    // Show that the station is being recorded, and where.
+                    if let Some(path) = recorder.path() {
+                        ui.colored_label(egui::Color32::RED, t(lang, "● REC"))
+                            .on_hover_text(path.display().to_string());
+                    }
+                }
+
+                // Copy a link that opens the app playing the station, on the
+                // web.
+                #[cfg(target_arch = "wasm32")]
+                if let Some(station) = playing_station {
+                    if ui
+                        .button("🔗")
+                        .labeled(t(lang, "Copy share link"))
+                        .clicked()
+                    {
+                        if let Some(link) = share_link(&station.stationuuid) {
+                            ui.output().copied_text = link;
+                        }
+                    }
+                }
+
+                // Vote for the playing station, once per session.
+                if let Some(station) = playing_station {
+                    if ui
+                        .add_enabled(
+                            !voted.contains(&station.stationuuid),
+                            egui::Button::new(format!("👍 {}", station.votes)),
+                        )
+                        .labeled(t(lang, "Vote for this station"))
+                        .on_disabled_hover_text(t(lang, "Already voted"))
+                        .clicked()
+                    {
+                        station_to_vote = Some(station.stationuuid.clone());
+                    }
+                }
+
+                /*
+                // Calculate the button width. This will be used for spacing.
+                let button_width = ui.spacing().interact_size.x;
+                // Calculate the available width.
+                let width = ui.available_width();
+
+                // let x = ui.spacing().interact_size.x * 1.5 + ui.spacing().slider_width
+                // ui.add_space(width - 4.0 * button_width);
+
+                // TODO: add more functionality and make consider small phone screen sizes.
+                // Also, vote for each station whenever it is played.
+                // Add button that adds current station to preferred stations.
+                if ui.button("➕♫").clicked() {}
+                if ui.button("➕🎵").clicked() {}
+                if ui.button("🎶").clicked() {}
+                // Add an options button.
+                if ui.button("📻").clicked() {}
+                */
+            });
+        });
+
+        // The central panel is the region left after adding top and
+        // side panels.
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::warn_if_debug_build(ui);
+
+            // Create a download guard.
+            let mut download_inner = download.lock().unwrap();
+
+            // Match the donwload state.
+            match &mut *download_inner {
+                // If no download, do nothing.
+                Download::None => {}
+                // If download in progress, show a spinner with the message,
+                // and keep repainting so the spinner turns and the stations
+                // show as soon as they arrive. Give up on the download if it
+                // takes too long, and let the user retry it. Its response is
+                // dropped if it arrives later.
+                Download::InProgress(fetch, _, request, started_at) => {
+                    if ui.input().time - *started_at >= f64::from(*request_timeout) {
+                        let err =
+                            format!("The request timed out after {} seconds.", request_timeout);
+                        notifications.error(t(lang, friendly_error(&err)));
+                        *download_inner = Download::Failed(*fetch, copy_request(request), err);
+                    } else {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::Spinner::new());
+                            ui.label(match fetch {
+                                Fetch::Replace => t(lang, "Retrieving stations…"),
+                                Fetch::Append => t(lang, "Loading more stations…"),
+                                Fetch::Refresh => t(lang, "Refreshing stations…"),
+                            });
+                        });
+                    }
+                    ctx.request_repaint();
+                }
+                // If the stations were downloaded, show them.
+                Download::Done(fetch, stations_vector) => {
+                    let fetch = *fetch;
+                    let stations_vector = std::mem::take(stations_vector);
+                    // A full page means the search may have more
+                    // stations.
+                    let page_len = stations_vector.len() as u32;
+                    *more_results = current_query
+                        .as_ref()
+                        .is_some_and(|query| page_len >= query.page_size());
+
+                    // Remove the stations with the same names and urls.
+                    let mut stations_vector = dedupe_stations(stations_vector);
+
+                    // Play the first station if it was requested, such
+                    // as when starting with a station UUID.
+                    if *play_first_result && fetch == Fetch::Replace {
+                        *play_first_result = false;
+                        match stations_vector.first() {
+                            Some(station) => {
+                                station_to_play = Some(station.clone());
+                            }
+                            None => {
+                                notifications.error(t(lang, "The station was not found."));
+                            }
+                        }
+                    }
+
+                    // Show the stations from the preferred country and
+                    // language first, keeping the order of the rest.
+                    rank_by_preference(&mut stations_vector, preferred_country, preferred_language);
+
+                    // Store the stations. The next page is added
+                    // after the shown stations, without repeating
+                    // them.
+                    let mut stations = stations.lock().unwrap();
+                    match fetch {
+                        Fetch::Replace | Fetch::Refresh => *stations = stations_vector,
+                        Fetch::Append => {
+                            stations.append(&mut stations_vector);
+                            *stations = dedupe_stations(std::mem::take(&mut *stations));
+                            if let Some(query) = current_query {
+                                query.next_page();
+                            }
+                        }
+                    }
+
+                    // Show there are no more downloads.
+                    *download_inner = Download::None;
+                }
+                // If the request failed, tell the user why in plain words,
+                // and let them retry it. The error itself is kept for
+                // debugging.
+                Download::Failed(_, _, err) => {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(egui::Color32::RED, t(lang, friendly_error(err)));
+                        if ui.button(t(lang, "Retry")).clicked() {
+                            retry_download = true;
+                        }
+                    });
+                    egui::CollapsingHeader::new(t(lang, "Details"))
+                        .id_source("download_error")
+                        .show(ui, |ui| {
+                            ui.label(err.as_str());
+                        });
+                }
+            }
+
+            // Show the favorite stations, so they can be played without
+            // searching for them.
+            if !favorites.is_empty() {
+                egui::CollapsingHeader::new(format!(
+                    "{} ({})",
+                    t(lang, "Favorites"),
+                    favorites.len()
+                ))
+                .id_source("favorites")
+                .show(ui, |ui| {
+                    // The favorite to remove, if the user asked for it.
+                    let mut removed = None;
+                    // The area of every favorite, to tell where a dragged
+                    // favorite is dropped.
+                    let mut rows = Vec::with_capacity(favorites.len());
+                    for (index, favorite) in favorites.iter().enumerate() {
+                        let row = ui.horizontal(|ui| {
+                            // Let the user drag the favorite by its handle, to
+                            // move it.
+                            let handle = ui
+                                .add(egui::Label::new("☰").sense(egui::Sense::drag()))
+                                .on_hover_text(t(lang, "Drag to reorder"));
+                            if handle.drag_started() {
+                                *dragged_favorite = Some(index);
+                            }
+                            if handle.hovered() || *dragged_favorite == Some(index) {
+                                ui.output().cursor_icon = egui::CursorIcon::Grab;
+                            }
+                            if ui
+                                .add_enabled(can_play, egui::Button::new("▶"))
+                                .labeled(t(lang, "Play"))
+                                .on_disabled_hover_text(
+                                    unavailable_reason.as_deref().unwrap_or_default(),
+                                )
+                                .clicked()
+                            {
+                                station_to_play = Some(favorite.clone());
+                            }
+                            favicons.show(ui, &favorite.favicon);
+                            selectable_text(ui, &favorite.name, "");
+                            if ui
+                                .small_button("✖")
+                                .labeled(t(lang, "Remove from favorites"))
+                                .clicked()
+                            {
+                                removed = Some(index);
+                            }
+                        });
+                        rows.push(row.response.rect);
+                    }
+
+                    // Show where the dragged favorite would be dropped, which
+                    // is before the first favorite below the pointer, and move
+                    // it there once released.
+                    if let Some(from) = *dragged_favorite {
+                        let pointer = ui.input().pointer.clone();
+                        if let (Some(position), Some(last)) = (pointer.interact_pos(), rows.last())
+                        {
+                            let before = rows
+                                .iter()
+                                .filter(|row| row.center().y < position.y)
+                                .count();
+                            let y = rows.get(before).map_or(last.bottom(), |row| row.top());
+                            ui.painter()
+                                .hline(last.x_range(), y, ui.visuals().selection.stroke);
+                            if pointer.any_released() {
+                                move_favorite(favorites, from, before);
+                            }
+                        }
+                        if pointer.any_released() || !pointer.any_down() {
+                            *dragged_favorite = None;
+                        }
+                    }
+
+                    if let Some(index) = removed {
+                        favorites.remove(index);
+                    }
+                });
+            }
+
+            // Show the recently played stations, so a station can be found
+            // again after its search is gone.
+            if !history.is_empty() {
+                egui::CollapsingHeader::new(format!("{} ({})", t(lang, "History"), history.len()))
+                    .id_source("history")
+                    .show(ui, |ui| {
+                        for played in history.iter() {
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .add_enabled(can_play, egui::Button::new("▶"))
+                                    .labeled(t(lang, "Play"))
+                                    .on_disabled_hover_text(
+                                        unavailable_reason.as_deref().unwrap_or_default(),
+                                    )
+                                    .clicked()
+                                {
+                                    station_to_play = Some(played.clone());
+                                }
+                                favicons.show(ui, &played.favicon);
+                                selectable_text(ui, &played.name, "");
+                            });
+                        }
+                    });
+            }
+
+            // Show the active filters as chips, so the user knows why some
+            // stations are hidden, and can clear the filters one by one.
+            let active_filters = broadcaster_filter.is_some() as usize
+                + state_filter.is_some() as usize
+                + country_facets.len()
+                + tag_facets.len()
+                + *hide_unreachable as usize
+                + *https_only as usize;
+            if active_filters > 0 {
+                egui::CollapsingHeader::new(format!(
+                    "{} ({})",
+                    t(lang, "Active filters"),
+                    active_filters
+                ))
+                .id_source("active_filters")
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        if let Some(domain) = broadcaster_filter.clone() {
+                            if ui
+                                .small_button(format!("{}: {} ✖", t(lang, "Broadcaster"), domain))
+                                .labeled(t(lang, "Clear filter"))
+                                .clicked()
+                            {
+                                *broadcaster_filter = None;
+                            }
+                        }
+                        if let Some(state) = state_filter.clone() {
+                            // Stations without a state are shown as "Unknown".
+                            let state = if state.is_empty() {
+                                t(lang, "Unknown")
+                            } else {
+                                &state
+                            };
+                            if ui
+                                .small_button(format!("{}: {} ✖", t(lang, "Region"), state))
+                                .labeled(t(lang, "Clear filter"))
+                                .clicked()
+                            {
+                                *state_filter = None;
+                            }
+                        }
+                        for country in country_facets.clone() {
+                            if ui
+                                .small_button(format!("{}: {} ✖", t(lang, "Country"), country))
+                                .labeled(t(lang, "Clear filter"))
+                                .clicked()
+                            {
+                                country_facets.remove(&country);
+                            }
+                        }
+                        for tag in tag_facets.clone() {
+                            if ui
+                                .small_button(format!("{}: {} ✖", t(lang, "Tag"), tag))
+                                .labeled(t(lang, "Clear filter"))
+                                .clicked()
+                            {
+                                tag_facets.remove(&tag);
+                            }
+                        }
+                        if *hide_unreachable
+                            && ui
+                                .small_button(format!("{} ✖", t(lang, "Hide unreachable stations")))
+                                .labeled(t(lang, "Clear filter"))
+                                .clicked()
+                        {
+                            *hide_unreachable = false;
+                        }
+                        if *https_only
+                            && ui
+                                .small_button(format!("{} ✖", t(lang, "HTTPS only")))
+                                .labeled(t(lang, "Clear filter"))
+                                .clicked()
+                        {
+                            *https_only = false;
+                        }
+                        if ui.button(t(lang, "Clear all filters")).clicked() {
+                            *broadcaster_filter = None;
+                            *state_filter = None;
+                            country_facets.clear();
+                            tag_facets.clear();
+                            *hide_unreachable = false;
+                            *https_only = false;
+                        }
+                    });
+                });
+            }
+
+            // If all the stations are from the same country, let the user
+            // narrow them by state/region.
+            {
+                let stations = stations.lock().unwrap();
+                if let Some(country) = single_country(&stations) {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} {}:", t(lang, "Region in"), country));
+
+                        // Show "All" when not filtering, and "Unknown" for
+                        // stations without a state.
+                        let selected_text = match state_filter.as_deref() {
+                            None => t(lang, "All"),
+                            Some("") => t(lang, "Unknown"),
+                            Some(state) => state,
+                        }
+                        .to_owned();
+
+                        egui::ComboBox::from_id_source("state_filter")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(state_filter, None, t(lang, "All"));
+                                for state in distinct_states(&stations) {
+                                    ui.selectable_value(
+                                        state_filter,
+                                        Some(state.to_owned()),
+                                        state,
+                                    );
+                                }
+                                ui.selectable_value(
+                                    state_filter,
+                                    Some(String::new()),
+                                    t(lang, "Unknown"),
+                                );
+                            });
+                    });
+                }
+            }
+
+            // Let the user narrow the stations by country and tag, without
+            // searching again.
+            {
+                let stations = stations.lock().unwrap();
+                if !stations.is_empty() {
+                    egui::CollapsingHeader::new(t(lang, "Filter results"))
+                        .id_source("facets")
+                        .show(ui, |ui| {
+                            let countries = facet_counts(
+                                stations.iter().map(|station| station.country.as_str()),
+                            );
+                            let tags = facet_counts(stations.iter().flat_map(station_tags));
+                            ui.columns(2, |columns| {
+                                columns[0].strong(t(lang, "Country"));
+                                facet_ui(
+                                    &mut columns[0],
+                                    "country_facets",
+                                    &countries,
+                                    country_facets,
+                                );
+                                columns[1].strong(t(lang, "Tag"));
+                                facet_ui(&mut columns[1], "tag_facets", &tags, tag_facets);
+                            });
+                        });
+                }
+            }
+
+            // Tell the user when a finished search found nothing, so it does
+            // not look like nothing was searched.
+            if let Some(query) = searched_text {
+                if matches!(*download_inner, Download::None) && stations.lock().unwrap().is_empty()
+                {
+                    ui.vertical_centered(|ui| {
+                        ui.label(format!(
+                            "{} '{}'. {}",
+                            t(lang, "No stations found for"),
+                            query,
+                            t(lang, "Try a different term or search mode.")
+                        ));
+                    });
+                }
+            }
+
+            // The stations checked before this time are unreachable, if
+            // they are hidden.
+            let checked_since = hide_unreachable.then(|| unix_time() - UNREACHABLE_AFTER);
+
+            // Let the user choose the order of the stations.
+            ui.horizontal(|ui| {
+                ui.label(t(lang, "Sort by: "));
+                egui::ComboBox::from_id_source("sort_by")
+                    .selected_text(t(lang, sort_by.label()))
+                    .show_ui(ui, |ui| {
+                        for order in SortBy::ALL {
+                            ui.selectable_value(sort_by, order, t(lang, order.label()));
+                        }
+                    });
+
+                // Let the user choose the columns of the stations grid.
+                ui.menu_button(t(lang, "Columns"), |ui| {
+                    for (shown, name) in columns.toggles() {
+                        ui.checkbox(shown, t(lang, name));
+                    }
+                });
+
+                // Let the user check many stations, to act on them at once.
+                // Leaving the selection mode forgets the checked stations.
+                if ui
+                    .selectable_label(*selection_mode, t(lang, "☑ Select"))
+                    .on_hover_text(t(
+                        lang,
+                        "Check stations to add them to the favorites at once",
+                    ))
+                    .clicked()
+                {
+                    *selection_mode = !*selection_mode;
+                    checked_stations.clear();
+                }
+
+                // Tell how many of the retrieved stations the filters show,
+                // for which search, and whether there are more to load.
+                let stations = stations.lock().unwrap();
+                if !stations.is_empty() {
+                    let shown = stations
+                        .iter()
+                        .filter(|station| {
+                            !blocked_stations.contains_key(&station.stationuuid)
+                                && is_shown(
+                                    station,
+                                    broadcaster_filter,
+                                    state_filter,
+                                    country_facets,
+                                    tag_facets,
+                                    checked_since,
+                                    *https_only,
+                                )
+                        })
+                        .count();
+                    let mut summary = format!(
+                        "{} {}/{} {}",
+                        t(lang, "Showing"),
+                        shown,
+                        stations.len(),
+                        t(lang, "stations")
+                    );
+                    if let Some(query) = searched_text {
+                        summary += &format!(" {} '{}'", t(lang, "for"), query);
+                    }
+                    if *more_results {
+                        summary += &format!(" ({})", t(lang, "more available"));
+                    }
+                    ui.label(summary);
+                }
+            });
+
+            // Let the user act on the checked stations.
+            if *selection_mode {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} {}",
+                        checked_stations.len(),
+                        t(lang, "selected")
+                    ));
+                    let has_checked = !checked_stations.is_empty();
+                    if ui
+                        .add_enabled(has_checked, egui::Button::new(t(lang, "Favorite selected")))
+                        .clicked()
+                    {
+                        let stations = stations.lock().unwrap();
+                        add_favorites(
+                            favorites,
+                            stations
+                                .iter()
+                                .filter(|station| checked_stations.contains(&station.stationuuid)),
+                        );
+                        checked_stations.clear();
+                    }
+                    if ui
+                        .add_enabled(has_checked, egui::Button::new(t(lang, "Clear selection")))
+                        .clicked()
+                    {
+                        checked_stations.clear();
+                    }
+                });
+            }
+
+            // The UUID of the station that is loaded, and whether it plays, so
+            // its row stands out.
+            let loaded_uuid = playing_station
+                .as_ref()
+                .map(|station| station.stationuuid.as_str());
+            let loaded_is_playing = *transport == Transport::Playing;
+
+            // Add a scroll area so the user can scroll through the stations.
+            egui::ScrollArea::vertical()
+                .max_width(f32::INFINITY)
+                .show(ui, |ui| {
+                    // Bring the widgets closer together when compact.
+                    let (min_col_width, grid_spacing) = match density {
+                        Density::Comfortable => (60.0, ui.spacing().item_spacing),
+                        Density::Compact => {
+                            ui.spacing_mut().item_spacing = egui::vec2(4.0, 0.0);
+                            ui.spacing_mut().button_padding = egui::vec2(2.0, 0.0);
+                            (40.0, egui::vec2(6.0, 0.0))
+                        }
+                    };
+
+                    // Add a grid where the stations will be placed.
+                    egui::Grid::new("stations")
+                        .striped(true)
+                        .min_col_width(min_col_width)
+                        .spacing(grid_spacing)
+                        .show(ui, |ui| {
+                            // Sort the stations without changing the radio
+                            // browser order, so it can be shown again.
+                            let stations = stations.lock().unwrap();
+                            let mut sorted_stations: Vec<&Station> = stations.iter().collect();
+                            sort_stations(&mut sorted_stations, *sort_by);
+
+                            // Name the shown columns, if there are
+                            // stations.
+                            if !sorted_stations.is_empty() {
+                                ui.label("");
+                                for (shown, name) in columns.toggles().into_iter().skip(1) {
+                                    if *shown {
+                                        ui.strong(t(lang, name));
+                                    }
+                                }
+                                ui.end_row();
+                            }
+
+                            // For every URL in the vector:
+                            for station in sorted_stations {
+                                // Skip the stations the filters leave out,
+                                // and the ones marked as broken.
+                                if blocked_stations.contains_key(&station.stationuuid)
+                                    || !is_shown(
+                                        station,
+                                        broadcaster_filter,
+                                        state_filter,
+                                        country_facets,
+                                        tag_facets,
+                                        checked_since,
+                                        *https_only,
+                                    )
+                                {
+                                    continue;
+                                }
+
+                                // Get the broadcaster domain of the station.
+                                let domain = homepage_domain(&station.homepage);
+
+                                // Create a group of components that will represent a link to a station.
+                                // Compact rows are not framed, to save room.
+                                let mut row_frame = match density {
+                                    Density::Comfortable => egui::Frame::group(ui.style()),
+                                    Density::Compact => egui::Frame::none(),
+                                };
+                                // Tint the row of the playing station, or
+                                // lightly the row of the selected station.
+                                let is_loaded = loaded_uuid == Some(station.stationuuid.as_str());
+                                let is_playing = is_loaded && loaded_is_playing;
+                                if is_playing {
+                                    row_frame = row_frame.fill(ui.visuals().selection.bg_fill);
+                                } else if is_loaded {
+                                    row_frame = row_frame.fill(ui.visuals().faint_bg_color);
+                                }
+                                let row = row_frame.show(ui, |ui| {
+                                    // Place the widgets horizontally.
+                                    ui.horizontal(|ui| {
+                                        // Let the user check the station in
+                                        // the selection mode.
+                                        if *selection_mode {
+                                            let mut is_checked =
+                                                checked_stations.contains(&station.stationuuid);
+                                            if ui.checkbox(&mut is_checked, "").changed() {
+                                                if is_checked {
+                                                    checked_stations
+                                                        .insert(station.stationuuid.clone());
+                                                } else {
+                                                    checked_stations.remove(&station.stationuuid);
+                                                }
+                                            }
+                                        }
+
+                                        // Add a play button for the station,
+                                        // or a stop button if it is playing.
+                                        if is_playing {
+                                            stop_requested |=
+                                                ui.button("⏹").labeled(t(lang, "Stop")).clicked();
+                                        } else if ui
+                                            .add_enabled(can_play, egui::Button::new("▶"))
+                                            .labeled(t(lang, "Play"))
+                                            .on_disabled_hover_text(
+                                                unavailable_reason.as_deref().unwrap_or_default(),
+                                            )
+                                            .clicked()
+                                        {
+                                            // Play the station, or only select
+                                            // it if the user chose so.
+                                            if *play_on_click {
+                                                station_to_play = Some(station.clone());
+                                            } else {
+                                                station_to_select = Some(station.clone());
+                                            }
+                                        }
+                                        // Show the station logo, if the
+                                        // user did not hide the logos.
+                                        if columns.favicon {
+                                            favicons.show(ui, &station.favicon);
+                                        }
+
+                                        // Show the station name, with the
+                                        // searched text in bold when searching
+                                        // by name.
+                                        let highlight = if *search_by == SearchBy::Name {
+                                            text_to_search.as_str()
+                                        } else {
+                                            ""
+                                        };
+                                        // Cut very long names, showing the
+                                        // whole name when hovered.
+                                        let name = truncate_text(&station.name, NAME_MAX_CHARS);
+                                        let name_response = selectable_text(ui, &name, highlight);
+                                        if matches!(name, std::borrow::Cow::Owned(_)) {
+                                            name_response.on_hover_text(&station.name);
+                                        }
+
+                                        // Show the quality of the stream, to
+                                        // spot the high quality streams.
+                                        quality_badge(ui, station);
+
+                                        // Mark the stations from the preferred
+                                        // country or language.
+                                        if preference_score(
+                                            station,
+                                            preferred_country,
+                                            preferred_language,
+                                        ) > 0
+                                        {
+                                            ui.label("🏠").on_hover_text(t(
+                                                lang,
+                                                "Preferred country or language",
+                                            ));
+                                        }
+
+                                        // Add a button that adds the station to
+                                        // the favorites, or removes it.
+                                        let is_favorite = favorites.iter().any(|favorite| {
+                                            favorite.stationuuid == station.stationuuid
+                                        });
+                                        let (icon, hover_text) = if is_favorite {
+                                            ("★", t(lang, "Remove from favorites"))
+                                        } else {
+                                            ("☆", t(lang, "Add to favorites"))
+                                        };
+                                        if ui.button(icon).labeled(hover_text).clicked() {
+                                            toggle_favorite(favorites, station);
+                                        }
+
+                                        // Add a button that shows the station
+                                        // details.
+                                        if ui
+                                            .button("ℹ")
+                                            .labeled(t(lang, "Station details"))
+                                            .clicked()
+                                        {
+                                            station_window.toggle(station);
+                                        }
+
+                                        // Add a button that opens the station
+                                        // homepage, such as to find its
+                                        // schedule.
+                                        homepage_button(ui, station)
+                                            .labeled(t(lang, "Open homepage"));
+
+                                        // Add a button that shows only the
+                                        // stations of the same broadcaster.
+                                        // Stations without a homepage have no
+                                        // broadcaster, so disable the button.
+                                        if ui
+                                            .add_enabled(domain.is_some(), egui::Button::new("🏢"))
+                                            .labeled(t(lang, "More from this broadcaster"))
+                                            .clicked()
+                                        {
+                                            *broadcaster_filter = domain;
+                                        }
+
+                                        // Add a button that creates a launcher
+                                        // that starts playing the station.
+                                        #[cfg(not(target_arch = "wasm32"))]
+                                        if ui
+                                            .button("📌")
+                                            .labeled(t(lang, "Create shortcut"))
+                                            .clicked()
+                                        {
+                                            match shortcut::create_station_shortcut(station) {
+                                                Ok(path) => notifications.info(tf(
+                                                    lang,
+                                                    "Created shortcut {}.",
+                                                    &[&path.display()],
+                                                )),
+                                                Err(err) => notifications.error(tf(
+                                                    lang,
+                                                    "Could not create shortcut: {}.",
+                                                    &[&err],
+                                                )),
+                                            }
                                         }
-                                        // Give a number to each station.
-                                        ui.label(&station.name);
                                     });
                                 });
+
+                                // Show the station details when hovering the
+                                // station, but not while scrolling, so that
+                                // tooltips do not flicker under the pointer.
+                                // Let the user copy the station details with a
+                                // right click, such as to share the station.
+                                let row_response = row
+                                    .response
+                                    .interact(egui::Sense::click())
+                                    .context_menu(|ui| {
+                                        let copy_stream = ui.button(t(lang, "Copy stream URL"));
+                                        let copy_homepage = ui.add_enabled(
+                                            !station.homepage.is_empty(),
+                                            egui::Button::new(t(lang, "Copy homepage")),
+                                        );
+                                        let copy_name = ui.button(t(lang, "Copy station name"));
+                                        // Let web users share a link that
+                                        // opens the app playing the station.
+                                        #[cfg(target_arch = "wasm32")]
+                                        if ui.button(t(lang, "Copy share link")).clicked() {
+                                            if let Some(link) = share_link(&station.stationuuid) {
+                                                ui.output().copied_text = link;
+                                            }
+                                            ui.close_menu();
+                                        }
+                                        // Let the user hide the station from
+                                        // the results for good, such as when
+                                        // it does not play.
+                                        if ui.button(t(lang, "Mark as broken")).clicked() {
+                                            blocked_stations.insert(
+                                                station.stationuuid.clone(),
+                                                station.name.clone(),
+                                            );
+                                            notifications.info(format!(
+                                                "{} {}",
+                                                t(lang, "Hidden:"),
+                                                station.name
+                                            ));
+                                            ui.close_menu();
+                                        }
+                                        let copied_text = if copy_stream.clicked() {
+                                            Some(station.stream_url())
+                                        } else if copy_homepage.clicked() {
+                                            Some(station.homepage.as_str())
+                                        } else if copy_name.clicked() {
+                                            Some(station.name.as_str())
+                                        } else {
+                                            None
+                                        };
+                                        if let Some(text) = copied_text {
+                                            ui.output().copied_text = text.to_owned();
+                                            ui.close_menu();
+                                        }
+                                    });
+                                if ui.input().scroll_delta == egui::Vec2::ZERO {
+                                    row_response
+                                        .on_hover_ui(|ui| station_tooltip_ui(ui, lang, station));
+                                }
+
+                                // Show the stream details in the shown
+                                // columns, leaving unknown details empty, so
+                                // the cells stay under their names.
+                                if columns.codec {
+                                    ui.label(&station.codec);
+                                }
+                                if columns.bitrate {
+                                    if station.bitrate > 0 {
+                                        ui.label(format!("{} kbps", station.bitrate));
+                                    } else {
+                                        ui.label("");
+                                    }
+                                }
+                                if columns.country {
+                                    ui.label(&station.country);
+                                }
+                                // Show the first few tags, which describe the
+                                // station best, cutting very long tags. Show
+                                // every tag when hovered.
+                                if columns.tags {
+                                    let tags: Vec<&str> = station
+                                        .tags
+                                        .split(',')
+                                        .map(str::trim)
+                                        .filter(|tag| !tag.is_empty())
+                                        .collect();
+                                    let first_tags = tags[..tags.len().min(3)].join(", ");
+                                    let shown = truncate_text(&first_tags, TAGS_MAX_CHARS);
+                                    let tags_response =
+                                        ui.label(egui::RichText::new(shown.as_ref()).small());
+                                    if tags.len() > 3 || matches!(shown, std::borrow::Cow::Owned(_))
+                                    {
+                                        tags_response.on_hover_text(tags.join(", "));
+                                    }
+                                }
+                                // Add a button that votes for the station,
+                                // once per session.
+                                if columns.votes
+                                    && ui
+                                        .add_enabled(
+                                            !voted.contains(&station.stationuuid),
+                                            egui::Button::new(format!("👍 {}", station.votes)),
+                                        )
+                                        .labeled(t(lang, "Vote for this station"))
+                                        .on_disabled_hover_text(t(lang, "Already voted"))
+                                        .clicked()
+                                {
+                                    station_to_vote = Some(station.stationuuid.clone());
+                                }
+
                                 // End the grid row.
                                 ui.end_row();
                             }
                         });
+
+                    // Let the user load the next page of the search, once the
+                    // current page is shown.
+                    if *more_results
+                        && matches!(*download_inner, Download::None)
+                        && ui.button(t(lang, "Load more")).clicked()
+                    {
+                        load_more = true;
+                    }
                 });
 
-            // If the user settings panel is open:
-            if *user_settings_is_open {
+            // If the options panel is open:
+            if *options_is_open {
                 // Show the side panel:
-                egui::SidePanel::right("side_panel").show(ctx, |ui| {
+                egui::SidePanel::right("options_panel").show(ctx, |ui| {
                     // Display the name of the panel.
-                    ui.heading("User Settings");
+                    ui.heading(t(lang, "Options"));
+
+                    // Let the user choose the volume set on launch, instead
+                    // of the last volume.
+                    let mut has_default_volume = default_volume.is_some();
+                    if ui
+                        .checkbox(&mut has_default_volume, t(lang, "Start at volume"))
+                        .changed()
+                    {
+                        *default_volume = has_default_volume.then_some(volume.on_slider);
+                    }
+                    if let Some(level) = default_volume {
+                        ui.add(egui::Slider::new(level, 0..=max_volume(*volume_boost)));
+                    }
+
+                    // Let the user choose how many stations a search retrieves.
+                    ui.horizontal(|ui| {
+                        ui.label(t(lang, "Results per search: "));
+                        ui.add(egui::DragValue::new(results_limit).clamp_range(1..=MAX_LIMIT));
+                    });
+
+                    // Let the user choose how long to wait for a search.
+                    ui.horizontal(|ui| {
+                        ui.label(t(lang, "Search timeout: "));
+                        ui.add(
+                            egui::DragValue::new(request_timeout)
+                                .clamp_range(5..=120)
+                                .suffix(" s"),
+                        );
+                    });
+
+                    // Let the user search without pressing Enter.
+                    ui.checkbox(search_as_you_type, t(lang, "Search as you type"));
 
-                    // Display a combo box with available languages.
+                    // Let the user choose stations without playing them, to
+                    // play them with the play/pause button or Space.
+                    ui.checkbox(play_on_click, t(lang, "Play immediately on station click"));
+
+                    // Let the user choose the radio browser server, such as
+                    // when only some servers can be reached from their
+                    // network. Typed hosts are checked before they are used.
+                    let mut server_changed = false;
                     ui.horizontal(|ui| {
-                        ui.label("Language: ");
-                        egui::ComboBox::from_label("🌎")
-                            // Display name of currently selected language.
-                            .selected_text(format!("{:?}", language))
+                        ui.label(t(lang, "Server: "));
+                        let selected_text = if server.is_empty() {
+                            t(lang, "Random").to_owned()
+                        } else {
+                            server.clone()
+                        };
+                        egui::ComboBox::from_id_source("server")
+                            .selected_text(selected_text)
                             .show_ui(ui, |ui| {
-                                ui.selectable_value(
-                                    language,
-                                    Language::English,
-                                    format!("{:?}", Language::English),
-                                );
-                                ui.selectable_value(
-                                    language,
-                                    Language::Spanish,
-                                    format!("{:?}", Language::Spanish),
-                                );
-                                ui.selectable_value(
-                                    language,
-                                    Language::Russian,
-                                    format!("{:?}", Language::Russian),
-                                );
+                                if ui
+                                    .selectable_label(server.is_empty(), t(lang, "Random"))
+                                    .clicked()
+                                {
+                                    server.clear();
+                                    server_changed = true;
+                                }
+                                for host in SERVERS {
+                                    if ui.selectable_label(server == host, host).clicked() {
+                                        *server = host.to_owned();
+                                        server_changed = true;
+                                    }
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        let field = ui.add(
+                            egui::TextEdit::singleline(server_input)
+                                .hint_text(t(lang, "Other server")),
+                        );
+                        if field.changed() {
+                            *server_input_is_invalid = false;
+                        }
+                        let submitted = (field.lost_focus()
+                            && ui.input().key_pressed(egui::Key::Enter))
+                            | ui.button(t(lang, "Use")).clicked();
+                        if submitted {
+                            if is_valid_host(server_input) {
+                                *server = server_input.trim().to_owned();
+                                server_input.clear();
+                                server_changed = true;
+                            } else {
+                                *server_input_is_invalid = true;
+                            }
+                        }
+                    });
+                    if *server_input_is_invalid {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            t(
+                                lang,
+                                "Enter a host name, such as de1.api.radio-browser.info.",
+                            ),
+                        );
+                    }
+                    if server_changed {
+                        use_server(server);
+                    }
+
+                    // Let the user fit more stations on the screen.
+                    ui.horizontal(|ui| {
+                        ui.label(t(lang, "Density: "));
+                        egui::ComboBox::from_id_source("density")
+                            .selected_text(t(lang, density.label()))
+                            .show_ui(ui, |ui| {
+                                for choice in Density::ALL {
+                                    ui.selectable_value(density, choice, t(lang, choice.label()));
+                                }
+                            });
+                    });
+
+                    // Let the user play the last played station, or a
+                    // favorite, when the application starts.
+                    ui.checkbox(autoplay_on_launch, t(lang, "Autoplay on launch"));
+                    if *autoplay_on_launch {
+                        let selected_text = default_station
+                            .as_ref()
+                            .map_or(t(lang, "Last played"), |station| station.name.as_str())
+                            .to_owned();
+                        ui.horizontal(|ui| {
+                            ui.label(t(lang, "Station on launch: "));
+                            egui::ComboBox::from_id_source("default_station")
+                                .selected_text(selected_text)
+                                .show_ui(ui, |ui| {
+                                    if ui
+                                        .selectable_label(
+                                            default_station.is_none(),
+                                            t(lang, "Last played"),
+                                        )
+                                        .clicked()
+                                    {
+                                        *default_station = None;
+                                    }
+                                    for favorite in favorites.iter() {
+                                        let is_selected =
+                                            default_station.as_ref().is_some_and(|station| {
+                                                station.stationuuid == favorite.stationuuid
+                                            });
+                                        if ui
+                                            .selectable_label(is_selected, &favorite.name)
+                                            .clicked()
+                                        {
+                                            *default_station = Some(favorite.clone());
+                                        }
+                                    }
+                                });
+                        });
+                    }
+
+                    // Let the user fade from one station to the next.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui
+                        .checkbox(crossfade, t(lang, "Cross-fade between stations"))
+                        .changed()
+                    {
+                        media_player.set_crossfade(*crossfade);
+                    }
+
+                    // Let the user ease the stations in, so loud stations do
+                    // not blast when they start.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.checkbox(fade_in, t(lang, "Fade in on play")).changed() {
+                        media_player.set_fade_in(*fade_in);
+                    }
+
+                    // Let the user choose how soon a new track title shows,
+                    // since asking often costs more.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.horizontal(|ui| {
+                        ui.label(t(lang, "Refresh the track title every"));
+                        if ui
+                            .add(
+                                egui::Slider::new(metadata_interval, METADATA_INTERVAL_RANGE)
+                                    .suffix(" s"),
+                            )
+                            .changed()
+                        {
+                            media_player.set_metadata_interval(*metadata_interval);
+                        }
+                    });
+
+                    // Let the user raise the volume above 100%, to amplify
+                    // quiet stations. Browsers cannot amplify the audio.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui
+                        .checkbox(volume_boost, t(lang, "Allow volume boost above 100%"))
+                        .changed()
+                        && !*volume_boost
+                    {
+                        // Lower the boosted levels back to 100%.
+                        volume.limit(100);
+                        media_player.set_volume(profile_volume(
+                            volume.on_slider,
+                            playing_profile.as_ref(),
+                            100,
+                        ));
+                        if let Some(level) = default_volume {
+                            *level = (*level).min(100);
+                        }
+                    }
+
+                    // Let the user restore the volume chosen for each station,
+                    // and forget the chosen volumes.
+                    ui.checkbox(
+                        remember_station_volumes,
+                        t(lang, "Remember the volume of each station"),
+                    );
+                    if ui
+                        .add_enabled(
+                            !station_volumes.is_empty(),
+                            egui::Button::new(format!(
+                                "{} ({})",
+                                t(lang, "Forget station volumes"),
+                                station_volumes.len()
+                            )),
+                        )
+                        .clicked()
+                    {
+                        station_volumes.clear();
+                    }
+
+                    // Let the user equalize the stations. VLC is restarted to
+                    // apply the equalizer, so the bands are only applied
+                    // once the user lets go of their sliders.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    egui::CollapsingHeader::new(t(lang, "Equalizer")).show(ui, |ui| {
+                        let mut apply = ui
+                            .checkbox(&mut equalizer.is_enabled, t(lang, "Equalize"))
+                            .changed();
+                        ui.add_enabled_ui(equalizer.is_enabled, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(t(lang, "Preset: "));
+                                egui::ComboBox::from_id_source("equalizer_preset")
+                                    .selected_text(t(lang, equalizer.preset.label()))
+                                    .show_ui(ui, |ui| {
+                                        for preset in equalizer::Preset::ALL {
+                                            if ui
+                                                .selectable_label(
+                                                    equalizer.preset == preset,
+                                                    t(lang, preset.label()),
+                                                )
+                                                .clicked()
+                                            {
+                                                equalizer.set_preset(preset);
+                                                apply = true;
+                                            }
+                                        }
+                                    });
+                            });
+                            ui.horizontal(|ui| {
+                                for (gain, frequency) in
+                                    equalizer.bands.iter_mut().zip(equalizer::BAND_FREQUENCIES)
+                                {
+                                    ui.vertical(|ui| {
+                                        let slider = ui.add(
+                                            egui::Slider::new(gain, equalizer::GAIN_RANGE)
+                                                .vertical()
+                                                .show_value(false),
+                                        );
+                                        ui.small(frequency);
+                                        if slider.changed() {
+                                            equalizer.preset = equalizer::Preset::Custom;
+                                        }
+                                        apply |= slider.drag_released()
+                                            || (slider.changed() && !slider.dragged());
+                                    });
+                                }
                             });
+                        });
+                        // A station with its own preset keeps it.
+                        let station_preset = playing_profile
+                            .as_ref()
+                            .and_then(|profile| profile.equalizer);
+                        if apply && station_preset.is_none() {
+                            media_player.set_equalizer(equalizer);
+                        }
+                    });
+
+                    // Let the user choose where the recordings are saved.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.horizontal(|ui| {
+                        ui.label(t(lang, "Recordings: "));
+                        let hint = recorder::default_directory()
+                            .map(|directory| directory.display().to_string())
+                            .unwrap_or_default();
+                        ui.add(egui::TextEdit::singleline(recordings_dir).hint_text(hint));
                     });
+
+                    // Let the user choose the audio device. VLC only lists
+                    // the devices once a station plays.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let devices = media_player.audio_devices();
+                        if devices.is_empty() {
+                            ui.label(t(lang, "Play a station to choose the audio device."));
+                        } else {
+                            let selected_text = devices
+                                .iter()
+                                .find(|device| device.id == *audio_device)
+                                .map_or(t(lang, "Default"), |device| device.name.as_str())
+                                .to_owned();
+                            ui.horizontal(|ui| {
+                                ui.label(t(lang, "Audio device: "));
+                                egui::ComboBox::from_id_source("audio_device")
+                                    .selected_text(selected_text)
+                                    .show_ui(ui, |ui| {
+                                        for device in &devices {
+                                            if ui
+                                                .selectable_value(
+                                                    audio_device,
+                                                    device.id.clone(),
+                                                    &device.name,
+                                                )
+                                                .changed()
+                                            {
+                                                media_player.set_audio_device(audio_device);
+                                            }
+                                        }
+                                    });
+                            });
+                        }
+                    }
                 });
             }
+
+            // If the user settings panel is open:
+            if *user_settings_is_open {
+                // Show the side panel. The user can resize it, and the width
+                // is remembered.
+                let panel = egui::SidePanel::right("side_panel")
+                    .resizable(true)
+                    .default_width(*settings_panel_width)
+                    .width_range(SETTINGS_PANEL_WIDTHS)
+                    .show(ctx, |ui| {
+                        // Display the name of the panel.
+                        ui.heading(t(lang, "User Settings"));
+
+                        // Display a combo box with available languages.
+                        ui.horizontal(|ui| {
+                            ui.label(t(lang, "Language: "));
+                            egui::ComboBox::from_label("🌎")
+                                // Display name of currently selected language.
+                                .selected_text(format!("{:?}", language))
+                                .show_ui(ui, |ui| {
+                                    for choice in Language::ALL {
+                                        ui.selectable_value(
+                                            language,
+                                            choice,
+                                            format!("{:?}", choice),
+                                        );
+                                    }
+                                });
+                        });
+
+                        // Let the radio browser leave out the broken stations,
+                        // which is cheaper than filtering them here.
+                        ui.checkbox(hide_broken, t(lang, "Hide broken (server-side)"))
+                            .on_hover_text(t(lang, "Applies to the next search"));
+
+                        // Let the user hide the stations that were not checked
+                        // as working in the last days.
+                        ui.checkbox(hide_unreachable, t(lang, "Hide unreachable stations"));
+
+                        // Let the user hide the stations that browsers may
+                        // block because they do not use HTTPS.
+                        ui.checkbox(https_only, t(lang, "HTTPS only"))
+                            .on_hover_text(t(lang, "Browsers block HTTP streams on secure pages"));
+
+                        // Let the user review the stations marked as broken,
+                        // and show them again.
+                        egui::CollapsingHeader::new(format!(
+                            "{} ({})",
+                            t(lang, "Broken stations"),
+                            blocked_stations.len()
+                        ))
+                        .id_source("blocked_stations")
+                        .show(ui, |ui| {
+                            if blocked_stations.is_empty() {
+                                ui.label(t(lang, "No station is marked as broken."));
+                            }
+                            let mut unblocked = None;
+                            for (uuid, name) in blocked_stations.iter() {
+                                ui.horizontal(|ui| {
+                                    if ui.small_button(t(lang, "Unblock")).clicked() {
+                                        unblocked = Some(uuid.clone());
+                                    }
+                                    ui.label(name).on_hover_text(uuid);
+                                });
+                            }
+                            if let Some(uuid) = unblocked {
+                                blocked_stations.remove(&uuid);
+                            }
+                        });
+
+                        // Let the user choose which stations are shown first.
+                        ui.horizontal(|ui| {
+                            ui.label(t(lang, "Preferred country: "));
+                            ui.add(
+                                egui::TextEdit::singleline(preferred_country).hint_text("Germany"),
+                            );
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(t(lang, "Preferred language: "));
+                            ui.add(
+                                egui::TextEdit::singleline(preferred_language).hint_text("german"),
+                            );
+                        });
+
+                        // Let the user choose the country of the local search,
+                        // such as when it cannot be detected.
+                        ui.horizontal(|ui| {
+                            ui.label(t(lang, "Local country code: "));
+                            ui.add(
+                                egui::TextEdit::singleline(local_country_code)
+                                    .hint_text("DE")
+                                    .desired_width(40.0),
+                            );
+                        });
+
+                        // Restore the search and filters on the next launch.
+                        ui.checkbox(restore_last_view, t(lang, "Restore last view"))
+                            .on_hover_text(t(lang, "Runs the last search again on launch"));
+
+                        ui.separator();
+
+                        // Ask for confirmation before resetting the settings.
+                        if ui.button(t(lang, "Reset all settings")).clicked() {
+                            *reset_dialog_is_open = true;
+                        }
+                    });
+                *settings_panel_width = panel.response.rect.width();
+            }
         });
+
+        // Show a dialog that confirms resetting the settings.
+        if *reset_dialog_is_open {
+            egui::Window::new(t(lang, "Reset settings"))
+                .id(egui::Id::new("reset_settings"))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(t(lang, "Restore every setting to its default value?"));
+                    ui.checkbox(
+                        reset_clears_saved,
+                        t(lang, "Also clear favorites and history"),
+                    );
+                    ui.horizontal(|ui| {
+                        if ui.button(t(lang, "Reset")).clicked() {
+                            reset_confirmed = true;
+                            *reset_dialog_is_open = false;
+                        }
+                        if ui.button(t(lang, "Cancel")).clicked() {
+                            *reset_dialog_is_open = false;
+                        }
+                    });
+                });
+        }
+
+        // Show a dialog that plays the stream URL the user types. The URL is
+        // checked first, so text that is not a URL is never played.
+        if *play_url_dialog_is_open {
+            let mut is_open = true;
+            let mut submitted = false;
+            egui::Window::new(t(lang, "Play URL"))
+                .id(egui::Id::new("play_url"))
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut is_open)
+                .show(ctx, |ui| {
+                    let field = ui.add(
+                        egui::TextEdit::singleline(url_to_play)
+                            .hint_text("https://example.com/stream.mp3"),
+                    );
+                    if field.changed() {
+                        *url_is_invalid = false;
+                    }
+                    submitted = field.lost_focus() && ui.input().key_pressed(egui::Key::Enter);
+                    if *url_is_invalid {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            t(lang, "Enter a URL that starts with http:// or https://."),
+                        );
+                    }
+                    submitted |= ui.button(t(lang, "Play")).clicked();
+                });
+            if submitted {
+                if is_stream_url(url_to_play) {
+                    // The stream has no profile.
+                    switch_profile(
+                        media_player.as_mut(),
+                        volume,
+                        max_volume(*volume_boost),
+                        #[cfg(not(target_arch = "wasm32"))]
+                        equalizer,
+                        playing_profile.as_ref(),
+                        None,
+                        false,
+                    );
+                    playing_profile = None;
+                    *mirrors = Mirrors::default();
+                    *station_url = url_to_play.trim().to_owned();
+                    play_url(media_player.as_mut(), station_url);
+                    *transport = Transport::Playing;
+                    // The stop was armed for a song of the previous station.
+                    *stop_after_track = None;
+                    // The stream has no name, so tell it apart from the
+                    // stations of the database.
+                    *now_playing = t(lang, "Custom stream").to_owned();
+                    *playing_station = None;
+                    url_to_play.clear();
+                    is_open = false;
+                } else {
+                    *url_is_invalid = true;
+                }
+            }
+            *play_url_dialog_is_open = is_open;
+        }
+
+        // Send the failed request again.
+        if retry_download {
+            let failed = std::mem::replace(&mut *download.lock().unwrap(), Download::None);
+            if let Download::Failed(fetch, request, _) = failed {
+                fetch_stations(ctx, download, notifications, lang, request, fetch);
+            }
+        }
+
+        // Retrieve the next page of the search, and add it to the stations
+        // once done. The offset of the search moves once the page arrives.
+        if load_more {
+            if let Some(query) = current_query {
+                let mut next_query = query.clone();
+                next_query.next_page();
+                fetch_stations(
+                    ctx,
+                    download,
+                    notifications,
+                    lang,
+                    build_search_request(&next_query),
+                    Fetch::Append,
+                );
+            }
+        }
+
+        // Stop the station, and unload it, so nothing is left to resume.
+        if stop_requested {
+            log::info!("Stopping {}", station_url);
+            *transport = Transport::stop(media_player.as_mut());
+            *mirrors = Mirrors::default();
+            station_url.clear();
+            now_playing.clear();
+            *playing_station = None;
+        }
+
+        // Load the station selected during this frame, if any, without
+        // playing it, stopping the playing station. The play/pause button
+        // plays it.
+        if let Some(station) = station_to_select {
+            *transport = Transport::select(media_player.as_mut());
+            let profile = station_profiles.get(&station.stationuuid).cloned();
+            switch_profile(
+                media_player.as_mut(),
+                volume,
+                max_volume(*volume_boost),
+                #[cfg(not(target_arch = "wasm32"))]
+                equalizer,
+                playing_profile.as_ref(),
+                profile.as_ref(),
+                false,
+            );
+            playing_profile = profile;
+            *station_url = preferred_stream(preferred_streams, &station).to_owned();
+            *mirrors = Mirrors::new(&station, &stations.lock().unwrap());
+            *now_playing = station.name.clone();
+            *playing_station = Some(station);
+        }
+
+        // Play the station chosen during this frame, if any. If stations
+        // cannot be played, tell the user why instead.
+        if let (Some(reason), Some(_)) = (&unavailable_reason, &station_to_play) {
+            notifications.error(reason.as_str());
+            station_to_play = None;
+        }
+        if let Some(station) = station_to_play {
+            // Restore the volume the user chose for the station, if
+            // remembered. Otherwise the volume stays as is.
+            let mut volume_restored = false;
+            if *remember_station_volumes {
+                if let Some(&level) = station_volumes.get(&station.stationuuid) {
+                    *volume = Volume::new(level);
+                    volume.limit(max_volume(*volume_boost));
+                    volume_restored = true;
+                }
+            }
+
+            // Apply the profile of the station before it plays, so it does
+            // not start at the volume of the previous station.
+            let profile = station_profiles.get(&station.stationuuid).cloned();
+            switch_profile(
+                media_player.as_mut(),
+                volume,
+                max_volume(*volume_boost),
+                #[cfg(not(target_arch = "wasm32"))]
+                equalizer,
+                playing_profile.as_ref(),
+                profile.as_ref(),
+                volume_restored,
+            );
+
+            // Get the station URL to be streamed, and play it. The other
+            // streams of the station are tried if it fails.
+            *station_url = preferred_stream(preferred_streams, &station).to_owned();
+            *mirrors = Mirrors::new(&station, &stations.lock().unwrap());
+            play_url(media_player.as_mut(), station_url);
+
+            // Show that the station plays, and its name. The stop was armed
+            // for a song of the previous station.
+            *transport = Transport::Playing;
+            *now_playing = station.name.clone();
+            *stop_after_track = None;
+
+            // Remember the station, so it can be played again later.
+            add_to_history(history, &station);
+
+            // Count the click in the radio browser.
+            register_click(&station);
+            *playing_station = Some(station);
+        }
+
+        // Vote for the station chosen during this frame, if any. The vote is
+        // shown right away, and taken back if the radio browser does not
+        // count it.
+        if let Some(uuid) = station_to_vote {
+            if voted.insert(uuid.clone()) {
+                change_votes(
+                    stations
+                        .lock()
+                        .unwrap()
+                        .iter_mut()
+                        .chain(playing_station.as_mut()),
+                    &uuid,
+                    1,
+                );
+                send_vote(ctx, &uuid, rejected_votes);
+            }
+        }
+
+        // Forget the chosen audio device once VLC lists the devices without
+        // it, such as when it was unplugged. VLC already plays through the
+        // default device then.
+        if !audio_device.is_empty() {
+            let devices = media_player.audio_devices();
+            if !devices.is_empty() && !devices.iter().any(|device| device.id == *audio_device) {
+                audio_device.clear();
+            }
+        }
+
+        // Take back the votes that were not counted, so the station can be
+        // voted for again.
+        for (uuid, reason) in rejected_votes.lock().unwrap().drain(..) {
+            voted.remove(&uuid);
+            change_votes(
+                stations
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .chain(playing_station.as_mut()),
+                &uuid,
+                -1,
+            );
+            notifications.error(tf(lang, "Could not vote for station: {}.", &[&reason]));
+        }
+
+        // Stop recording once another station plays, or the station stops.
+        #[cfg(not(target_arch = "wasm32"))]
+        if recorder
+            .url()
+            .is_some_and(|url| url != station_url.as_str() || *transport != Transport::Playing)
+        {
+            recorder.stop();
+        }
+
+        // Tell the user once why the station could not be played. The reason
+        // stays next to the play button until another station plays.
+        let playback_error = match media_player.state() {
+            PlaybackState::Error(reason) => Some(reason),
+            _ => None,
+        };
+        if playback_error != *reported_playback_error && !failover_pending {
+            if let Some(reason) = &playback_error {
+                notifications.error(reason.clone());
+            }
+            *reported_playback_error = playback_error;
+        }
+
+        // Show the messages to the user above every panel.
+        notifications.show(ctx);
+
+        // Let screen readers know what is playing, once the text settles.
+        announcer.update(ctx, lang, &now_playing_text);
+
+        // Reset the settings once the panels no longer borrow them.
+        if reset_confirmed {
+            self.reset_settings();
+            ctx.set_visuals(theme(self.dark_mode));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A media player that remembers what it was told to do.
+    #[derive(Default)]
+    struct FakePlayer {
+        src: String,
+        playing: bool,
+        crossfade: bool,
+        /// The state of the player each time play() was called.
+        states_at_play: Vec<PlaybackState>,
+        /// The volumes passed to the player.
+        volumes: Vec<i32>,
+        /// The title of the playing track, if the station sends it.
+        track_title: Option<String>,
+    }
+
+    impl Player for FakePlayer {
+        fn set_src(&mut self, url: &str) {
+            self.src = url.to_owned();
+        }
+
+        fn play(&mut self) {
+            self.states_at_play.push(self.state());
+            self.playing = true;
+        }
+
+        fn set_crossfade(&mut self, enabled: bool) {
+            self.crossfade = enabled;
+        }
+
+        fn pause(&mut self) {
+            self.playing = false;
+        }
+
+        fn set_volume(&mut self, volume: i32) {
+            self.volumes.push(volume);
+        }
+
+        fn track_title(&self) -> Option<String> {
+            self.track_title.clone()
+        }
+
+        fn state(&self) -> PlaybackState {
+            if self.playing {
+                PlaybackState::Playing
+            } else {
+                PlaybackState::Idle
+            }
+        }
+    }
+
+    /// Return an app with the default state, which plays with a fake media
+    /// player, so no VLC is started.
+    fn test_app() -> App {
+        App::with_player(Box::<FakePlayer>::default())
+    }
+
+    fn station(name: &str, url: &str, url_resolved: &str, votes: i32) -> Station {
+        Station {
+            name: name.to_owned(),
+            url: url.to_owned(),
+            url_resolved: url_resolved.to_owned(),
+            votes,
+            ..Station::default()
+        }
+    }
+
+    #[test]
+    fn stations_with_the_same_name_and_resolved_url_are_merged() {
+        let stations = vec![
+            station("Jazz FM", "http://a/list.m3u", "http://a/stream", 1),
+            station("Jazz FM", "http://b/list.pls", "http://a/stream", 0),
+        ];
+        let unique = dedupe_stations(stations);
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[0].url, "http://a/list.m3u");
+    }
+
+    #[test]
+    fn most_voted_station_is_kept() {
+        let stations = vec![
+            station("Jazz FM", "http://a/stream", "", 3),
+            station("Jazz FM", "http://a/stream", "", 9),
+            station("Jazz FM", "http://a/stream", "", 5),
+        ];
+        let unique = dedupe_stations(stations);
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[0].votes, 9);
+    }
+
+    #[test]
+    fn merged_stations_keep_the_first_position() {
+        let stations = vec![
+            station("Jazz FM", "http://a/stream", "", 1),
+            station("Rock FM", "http://b/stream", "", 1),
+            station("Jazz FM", "http://a/stream", "", 7),
+        ];
+        let names: Vec<_> = dedupe_stations(stations)
+            .into_iter()
+            .map(|station| (station.name, station.votes))
+            .collect();
+        assert_eq!(
+            names,
+            [("Jazz FM".to_owned(), 7), ("Rock FM".to_owned(), 1)]
+        );
+    }
+
+    #[test]
+    fn stations_with_different_urls_are_kept() {
+        let stations = vec![
+            station("Jazz FM", "http://a/stream", "", 1),
+            station("Jazz FM", "http://b/stream", "", 1),
+        ];
+        assert_eq!(dedupe_stations(stations).len(), 2);
+    }
+
+    #[test]
+    fn toggling_a_playing_station_pauses_it() {
+        let mut player = FakePlayer::default();
+        player.play();
+        let transport = Transport::Playing.toggle(&mut player, "http://a/stream");
+        assert_eq!(transport, Transport::Paused);
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn toggling_a_paused_station_plays_it_again() {
+        let mut player = FakePlayer::default();
+        let transport = Transport::Paused.toggle(&mut player, "http://a/stream");
+        assert_eq!(transport, Transport::Playing);
+        assert_eq!(player.src, "http://a/stream");
+        assert!(player.is_playing());
+    }
+
+    #[test]
+    fn stopping_unloads_the_station() {
+        let mut player = FakePlayer::default();
+        player.play();
+        assert_eq!(Transport::stop(&mut player), Transport::Stopped);
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn selecting_a_station_loads_it_paused() {
+        let mut player = FakePlayer::default();
+        player.play();
+        let transport = Transport::select(&mut player);
+        assert_eq!(transport, Transport::Paused);
+        assert!(!player.is_playing());
+        assert_eq!(
+            transport.toggle(&mut player, "http://b/stream"),
+            Transport::Playing
+        );
+    }
+
+    #[test]
+    fn downloaded_stations_are_listed() {
+        let ctx = egui::Context::default();
+        let mut app = test_app();
+        let run_frame = |app: &mut App| {
+            let _ = ctx.run(egui::RawInput::default(), |ctx| app.ui(ctx));
+        };
+        for _ in 0..3 {
+            run_frame(&mut app);
+        }
+
+        let stations = vec![
+            station("Jazz FM", "http://a/stream", "", 1),
+            station("Rock FM", "http://b/stream", "", 1),
+        ];
+        *app.download.lock().unwrap() = Download::Done(Fetch::Replace, stations);
+        for _ in 0..3 {
+            run_frame(&mut app);
+        }
+
+        let names: Vec<_> = app
+            .stations
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|station| station.name.clone())
+            .collect();
+        assert_eq!(names, ["Jazz FM", "Rock FM"]);
+        assert!(matches!(*app.download.lock().unwrap(), Download::None));
+    }
+
+    #[test]
+    fn only_cut_text_is_owned() {
+        // The ellipsis takes three bytes, so the cut text is longer in bytes
+        // than the whole one.
+        let cut = truncate_text("Radio1", 5);
+        assert_eq!(cut, "Radi…");
+        assert!(cut.len() > "Radio1".len());
+        assert!(matches!(cut, std::borrow::Cow::Owned(_)));
+        assert!(matches!(
+            truncate_text("Radio", 5),
+            std::borrow::Cow::Borrowed("Radio")
+        ));
+    }
+
+    /// Return an app with some settings changed, a favorite, and a history.
+    fn customized_app() -> App {
+        App {
+            sort_by: SortBy::Votes,
+            search_order: SearchOrder::Votes,
+            min_bitrate: 128,
+            codec_filter: "MP3".to_owned(),
+            settings_panel_width: 320.0,
+            dark_mode: false,
+            text_to_search: "jazz".to_owned(),
+            favorites: vec![station("Jazz FM", "http://a/stream", "", 1)],
+            history: VecDeque::from([station("Rock FM", "http://b/stream", "", 1)]),
+            ..test_app()
+        }
+    }
+
+    #[test]
+    fn resetting_restores_every_setting() {
+        let mut app = customized_app();
+        app.reset_settings();
+        assert_eq!(app.sort_by, SortBy::Relevance);
+        assert_eq!(app.search_order, SearchOrder::Relevance);
+        assert_eq!(app.min_bitrate, 0);
+        assert_eq!(app.codec_filter, "");
+        assert_eq!(app.settings_panel_width, test_app().settings_panel_width);
+        assert!(app.dark_mode);
+        // The search is not a setting.
+        assert_eq!(app.text_to_search, "jazz");
+    }
+
+    #[test]
+    fn resetting_keeps_favorites_and_history_unless_asked() {
+        let mut app = customized_app();
+        app.reset_settings();
+        assert_eq!(app.favorites.len(), 1);
+        assert_eq!(app.history.len(), 1);
+
+        let mut app = customized_app();
+        app.reset_clears_saved = true;
+        app.reset_settings();
+        assert!(app.favorites.is_empty());
+        assert!(app.history.is_empty());
+    }
+
+    #[test]
+    fn playing_another_station_replaces_the_playing_one() {
+        let mut player = FakePlayer::default();
+        player.set_crossfade(true);
+        play_url(&mut player, "http://a/stream");
+        play_url(&mut player, "http://b/stream");
+        // The first station is still playing when the second one starts, so
+        // the player can fade it out.
+        assert_eq!(
+            player.states_at_play,
+            [PlaybackState::Idle, PlaybackState::Playing]
+        );
+        assert!(player.crossfade);
+        assert_eq!(player.src, "http://b/stream");
+    }
+
+    #[test]
+    fn the_stream_that_played_is_preferred() {
+        let mut preferred = HashMap::new();
+        let mut jazz = station("Jazz FM", "http://a/list.m3u", "http://a/stream", 1);
+        jazz.stationuuid = "jazz".to_owned();
+        assert_eq!(preferred_stream(&preferred, &jazz), "http://a/stream");
+
+        prefer_stream(&mut preferred, &jazz, "http://b/stream");
+        assert_eq!(preferred_stream(&preferred, &jazz), "http://b/stream");
+
+        // The own stream of the station needs no preference.
+        prefer_stream(&mut preferred, &jazz, "http://a/stream");
+        assert!(preferred.is_empty());
+    }
+
+    #[test]
+    fn stop_after_song_pauses_once_the_title_changes() {
+        let mut player = FakePlayer::default();
+        play_url(&mut player, "http://a/stream");
+        player.track_title = Some("Artist - Song".to_owned());
+        let mut transport = Transport::Playing;
+        let mut armed = Some("Artist - Song".to_owned());
+
+        // The song still plays, or the title is not known for a moment.
+        stop_after_song(&mut player, &mut transport, &mut armed);
+        player.track_title = None;
+        stop_after_song(&mut player, &mut transport, &mut armed);
+        assert_eq!(transport, Transport::Playing);
+        assert!(armed.is_some());
+
+        player.track_title = Some("Artist - Next song".to_owned());
+        stop_after_song(&mut player, &mut transport, &mut armed);
+        assert_eq!(transport, Transport::Paused);
+        assert_eq!(player.state(), PlaybackState::Idle);
+        assert!(armed.is_none());
+    }
+
+    #[test]
+    fn stop_after_song_is_disarmed_once_paused() {
+        let mut player = FakePlayer::default();
+        let mut transport = Transport::Paused;
+        let mut armed = Some("Artist - Song".to_owned());
+        stop_after_song(&mut player, &mut transport, &mut armed);
+        assert!(armed.is_none());
+    }
+
+    #[test]
+    fn profile_volume_is_sent_only_when_it_changes() {
+        let mut player = FakePlayer::default();
+        let volume = Volume::new(50);
+        #[cfg(not(target_arch = "wasm32"))]
+        let equalizer = equalizer::Equalizer::default();
+        let louder = StationProfile {
+            volume_offset: 20,
+            ..StationProfile::default()
+        };
+        let mut switch = |from, to, volume_restored| {
+            switch_profile(
+                &mut player,
+                &volume,
+                100,
+                #[cfg(not(target_arch = "wasm32"))]
+                &equalizer,
+                from,
+                to,
+                volume_restored,
+            )
+        };
+        switch(None, Some(&louder), false);
+        switch(Some(&louder), Some(&louder), false);
+        switch(Some(&louder), None, false);
+        switch(None, None, false);
+        switch(None, None, true);
+        assert_eq!(player.volumes, [70, 50, 50]);
     }
 }
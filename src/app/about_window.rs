@@ -1,16 +1,16 @@
+use super::translation::{t, tf};
+use super::window::show_window;
+use super::Language;
 use eframe::egui;
 
 /// The About window shows information about the application, such as creator
 /// names, source code links, and technologies used.
 /// It derives Deserialize/Serialize so it can persist app state on shutdown.
 #[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
-/// New fields are are given default values when deserializing old state.
+/// New fields are given default values when deserializing old state.
 // #[cfg_attr(feature = "persistence", serde(default))]
 pub struct AboutWindow {
-    /// The name of the window.
-    name: String,
-
-    /// Wether the window is open or closed.
+    /// Whether the window is open or closed.
     pub is_open: bool,
 }
 
@@ -19,59 +19,66 @@ impl Default for AboutWindow {
     /// Create default window.
     fn default() -> Self {
         AboutWindow {
-            // Name the About window.
-            name: "About".to_owned(),
-
             // Set the window closed by default.
             is_open: false,
         }
     }
 }
 
-/// Define function for running app natively and on web.
-impl eframe::App for AboutWindow {
-    /// Called each time the UI needs repainting
-    fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
+impl AboutWindow {
+    /// Show the window, if it is open, in the given language.
+    pub fn show(&mut self, ctx: &egui::Context, lang: Language) {
         // Create an About window. The window only pops up when the About menu
         // itme is pressed.
-        egui::Window::new(self.name.to_string())
-            .open(&mut self.is_open)
-            .show(ctx, |ui| {
-                // Display the name of the application.
-                ui.vertical_centered(|ui| {
-                    ui.heading("ℹ Online Radio");
-                });
+        show_window(ctx, t(lang, "About"), &mut self.is_open, |ui| {
+            // Display the name of the application.
+            ui.vertical_centered(|ui| {
+                ui.heading("ℹ Online Radio");
+            });
 
-                // Display the name of the creators.
-                ui.label("🔨 Created by Luis David Licea Torres.");
+            // Display the version and the commit it was built from, so users
+            // can tell which build they report issues about.
+            ui.vertical_centered(|ui| {
+                ui.label(tf(
+                    lang,
+                    "Version {} ({})",
+                    &[&env!("CARGO_PKG_VERSION"), &env!("GIT_SHORT_HASH")],
+                ));
+            });
+
+            // Display the name of the creators.
+            ui.label(format!(
+                "🔨 {}",
+                tf(lang, "Created by {}.", &[&"Luis David Licea Torres"])
+            ));
 
-                // Display the source code link.
-                ui.horizontal(|ui| {
-                    // Remove the horizontal spacing so that labels and
-                    // hyperlinks are next to each other.
-                    ui.spacing_mut().item_spacing.x = 0.0;
+            // Display the source code link.
+            ui.horizontal(|ui| {
+                // Remove the horizontal spacing so that labels and
+                // hyperlinks are next to each other.
+                ui.spacing_mut().item_spacing.x = 0.0;
 
-                    ui.label(" Source code available at ");
-                    ui.hyperlink_to(
-                        "github.com/Luis-Licea/radio",
-                        "https://github.com/Luis-Licea/radio",
-                    );
-                    ui.label(".");
-                });
+                ui.label(format!(" {} ", t(lang, "Source code available at")));
+                ui.hyperlink_to(
+                    "github.com/Luis-Licea/radio",
+                    "https://github.com/Luis-Licea/radio",
+                );
+                ui.label(".");
+            });
 
-                // Display the techonologies used to create the application.
-                // Powered by ...
-                ui.horizontal(|ui| {
-                    // Remove the horizontal spacing so that labels and
-                    // hyperlinks are next to each other.
-                    ui.spacing_mut().item_spacing.x = 0.0;
+            // Display the techonologies used to create the application.
+            // Powered by ...
+            ui.horizontal(|ui| {
+                // Remove the horizontal spacing so that labels and
+                // hyperlinks are next to each other.
+                ui.spacing_mut().item_spacing.x = 0.0;
 
-                    ui.label("🔥 Powered by ");
-                    ui.hyperlink_to("egui", "https://github.com/emilk/egui");
-                    ui.label(" and ");
-                    ui.hyperlink_to("eframe", "https://github.com/emilk/egui/tree/master/eframe");
-                    ui.label(".");
-                });
+                ui.label(format!("🔥 {} ", t(lang, "Powered by")));
+                ui.hyperlink_to("egui", "https://github.com/emilk/egui");
+                ui.label(format!(" {} ", t(lang, "and")));
+                ui.hyperlink_to("eframe", "https://github.com/emilk/egui/tree/master/eframe");
+                ui.label(".");
             });
+        });
     }
 }
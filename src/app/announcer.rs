@@ -0,0 +1,55 @@
+use super::translation::tf;
+use super::Language;
+use eframe::egui;
+
+/// How long, in seconds, the now-playing text must stay the same before it is
+/// announced. Stations may change their metadata several times in a row, and
+/// each change should not interrupt the screen reader.
+const ANNOUNCEMENT_DELAY: f64 = 1.0;
+
+/// Announces the now-playing text to screen readers when it changes, like a
+/// live region does on the web.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    /// The text waiting to be announced, and the time it was first seen.
+    pending: Option<(String, f64)>,
+
+    /// The last announced text.
+    announced: String,
+}
+
+impl Announcer {
+    /// Announce the text once it has stayed the same for a moment. Call once
+    /// per frame, after the panels are drawn, so the announcement is the last
+    /// output event, which is the one screen readers describe. The text is
+    /// announced in the given language.
+    pub fn update(&mut self, ctx: &egui::Context, lang: Language, text: &str) {
+        let now = ctx.input().time;
+
+        // Restart the wait whenever the text changes.
+        if text == self.announced {
+            self.pending = None;
+        } else if !matches!(&self.pending, Some((pending, _)) if pending == text) {
+            self.pending = Some((text.to_owned(), now));
+        }
+
+        if let Some((pending, since)) = &self.pending {
+            if now - since >= ANNOUNCEMENT_DELAY {
+                ctx.output()
+                    .events
+                    .push(egui::output::OutputEvent::ValueChanged(
+                        egui::WidgetInfo::labeled(
+                            egui::WidgetType::Label,
+                            tf(lang, "Now playing: {}", &[pending]),
+                        ),
+                    ));
+                self.announced = pending.clone();
+                self.pending = None;
+            } else {
+                // Keep repainting, so the text is announced even if the user
+                // does not move the mouse.
+                ctx.request_repaint();
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(feature = "persistence")]
+use super::App;
+#[cfg(feature = "persistence")]
+use std::collections::HashMap;
+#[cfg(feature = "persistence")]
+use std::sync::Mutex;
+#[cfg(feature = "persistence")]
+use std::time::{Duration, Instant};
+
+/// How often the app state is copied, for saving on a panic.
+#[cfg(feature = "persistence")]
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The last copy of the app state, as saved by eframe, and when it was taken.
+/// The app is borrowed by eframe when it panics, so the panic hook saves this
+/// copy instead.
+#[cfg(feature = "persistence")]
+static SNAPSHOT: Mutex<Option<(Instant, String)>> = Mutex::new(None);
+
+/// Copy the app state, for saving on a panic, unless it was copied a moment
+/// ago. Call once per frame.
+#[cfg(feature = "persistence")]
+pub fn keep_snapshot(app: &App) {
+    let mut snapshot = match SNAPSHOT.try_lock() {
+        Ok(snapshot) => snapshot,
+        Err(_) => return,
+    };
+    if matches!(&*snapshot, Some((taken_at, _)) if taken_at.elapsed() < SNAPSHOT_INTERVAL) {
+        return;
+    }
+    if let Ok(state) = ron::ser::to_string(app) {
+        *snapshot = Some((Instant::now(), state));
+    }
+}
+
+/// Log the panics, and save the last copy of the app state where eframe
+/// saves it, so a crash does not lose the favorites, the history, and the
+/// settings changed since eframe last saved them. The state is saved under
+/// the name of the app, as given to eframe.
+pub fn install_panic_hook(app_name: &'static str) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        log::error!("The app panicked: {}", panic_info);
+        #[cfg(feature = "persistence")]
+        if let Err(err) = save_snapshot(app_name) {
+            log::error!("Could not save the app state: {}", err);
+        }
+        #[cfg(not(feature = "persistence"))]
+        let _ = app_name;
+        previous(panic_info);
+    }));
+}
+
+/// Save the last copy of the app state, if any, where eframe saves it.
+#[cfg(feature = "persistence")]
+fn save_snapshot(app_name: &str) -> Result<(), String> {
+    // The panic may have happened while the state was copied, so do not
+    // wait for the copy.
+    let state = match SNAPSHOT.try_lock() {
+        Ok(snapshot) => match &*snapshot {
+            Some((_, state)) => state.clone(),
+            None => return Ok(()),
+        },
+        Err(_) => return Err("the app state was being copied".to_owned()),
+    };
+    let dirs = directories_next::ProjectDirs::from("", "", app_name)
+        .ok_or("the data directory is unknown")?;
+    let path = dirs.data_dir().join("app.ron");
+    let saved = std::fs::read_to_string(&path).ok();
+    let contents = with_app_state(saved.as_deref(), state)?;
+    std::fs::write(&path, contents).map_err(|err| err.to_string())
+}
+
+/// Return the file saved by eframe, if any, with the app state replaced. The
+/// state of egui, such as the window size, is kept.
+#[cfg(feature = "persistence")]
+fn with_app_state(saved: Option<&str>, state: String) -> Result<String, String> {
+    let mut entries: HashMap<String, String> = saved
+        .and_then(|saved| ron::from_str(saved).ok())
+        .unwrap_or_default();
+    entries.insert(eframe::APP_KEY.to_owned(), state);
+    ron::ser::to_string_pretty(&entries, ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(all(test, feature = "persistence"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_state_is_replaced_and_egui_state_kept() {
+        let saved = HashMap::from([
+            (eframe::APP_KEY.to_owned(), "(old)".to_owned()),
+            ("egui".to_owned(), "(window)".to_owned()),
+        ]);
+        let saved = ron::ser::to_string(&saved).unwrap();
+
+        let saved = with_app_state(Some(&saved), "(new)".to_owned()).unwrap();
+        let saved: HashMap<String, String> = ron::from_str(&saved).unwrap();
+        assert_eq!(saved[eframe::APP_KEY], "(new)");
+        assert_eq!(saved["egui"], "(window)");
+    }
+
+    #[test]
+    fn app_state_is_saved_without_a_saved_file() {
+        let saved = with_app_state(None, "(new)".to_owned()).unwrap();
+        let saved: HashMap<String, String> = ron::from_str(&saved).unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[eframe::APP_KEY], "(new)");
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Return the directory of the cached responses of the given kind, such as
+/// "stations" or "favicons".
+fn cache_dir(kind: &str) -> Option<PathBuf> {
+    let dirs = directories_next::ProjectDirs::from("", "", "radio")?;
+    Some(dirs.cache_dir().join(kind))
+}
+
+/// Return the path of the cached response for the URL. The URL is hashed, so
+/// it can be used as a file name. The hash may change with the Rust version,
+/// which only makes the older responses miss.
+fn cache_path(kind: &str, url: &str) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    Some(cache_dir(kind)?.join(format!("{:016x}", hasher.finish())))
+}
+
+/// Return the cached response for the URL, if it was stored less than the
+/// given time ago.
+pub fn read(kind: &str, url: &str, max_age: Duration) -> Option<Vec<u8>> {
+    let path = cache_path(kind, url)?;
+    let modified = std::fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    if age > max_age {
+        return None;
+    }
+    std::fs::read(path).ok()
+}
+
+/// Store the response for the URL. The cache only saves time, so a response
+/// that cannot be stored is not an error.
+pub fn write(kind: &str, url: &str, bytes: &[u8]) {
+    if let Some(path) = cache_path(kind, url) {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(path, bytes);
+    }
+}
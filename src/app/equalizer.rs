@@ -0,0 +1,116 @@
+/// The number of frequency bands of the VLC equalizer.
+pub const BANDS: usize = 10;
+
+/// The frequencies of the bands, as shown under the band sliders.
+pub const BAND_FREQUENCIES: [&str; BANDS] = [
+    "60", "170", "310", "600", "1k", "3k", "6k", "12k", "14k", "16k",
+];
+
+/// The lowest and highest gain of a band, in decibels.
+pub const GAIN_RANGE: std::ops::RangeInclusive<f32> = -20.0..=20.0;
+
+/// The amplification VLC applies before the bands when none of them is
+/// boosted, in decibels. Boosted bands lower it, so they do not clip.
+const PREAMP: f32 = 12.0;
+
+/// The equalizer presets. Changing a band by hand makes the equalizer custom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub enum Preset {
+    Flat,
+    Rock,
+    Pop,
+    Jazz,
+    BassBoost,
+    /// The bands were changed by hand.
+    Custom,
+}
+
+impl Preset {
+    /// Every preset the user can choose, in the order they are shown.
+    pub const ALL: [Preset; 5] = [
+        Preset::Flat,
+        Preset::Rock,
+        Preset::Pop,
+        Preset::Jazz,
+        Preset::BassBoost,
+    ];
+
+    /// Return the English name of the preset.
+    pub fn label(self) -> &'static str {
+        match self {
+            Preset::Flat => "Flat",
+            Preset::Rock => "Rock",
+            Preset::Pop => "Pop",
+            Preset::Jazz => "Jazz",
+            Preset::BassBoost => "Bass Boost",
+            Preset::Custom => "Custom",
+        }
+    }
+
+    /// Return the gains of the bands of the preset, in decibels, or None for
+    /// the custom preset, which has no fixed gains.
+    fn bands(self) -> Option<[f32; BANDS]> {
+        match self {
+            Preset::Flat => Some([0.0; BANDS]),
+            Preset::Rock => Some([8.0, 4.8, -5.6, -8.0, -3.2, 4.0, 8.8, 11.2, 11.2, 11.2]),
+            Preset::Pop => Some([-1.6, 4.8, 7.2, 8.0, 5.6, 0.0, -2.4, -2.4, -1.6, -1.6]),
+            Preset::Jazz => Some([4.0, 3.0, 1.0, 2.0, -1.6, -1.6, 0.0, 1.6, 3.0, 4.0]),
+            Preset::BassBoost => Some([9.6, 8.0, 5.6, 2.4, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]),
+            Preset::Custom => None,
+        }
+    }
+}
+
+/// The equalizer applied to the stations played with VLC.
+#[derive(Debug, Clone, PartialEq)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub struct Equalizer {
+    /// Whether the stations are equalized.
+    pub is_enabled: bool,
+
+    /// The chosen preset, or Custom if a band was changed by hand.
+    pub preset: Preset,
+
+    /// The gains of the bands, in decibels.
+    pub bands: [f32; BANDS],
+}
+
+/// Implement trait to create the default equalizer.
+impl Default for Equalizer {
+    /// Create a disabled, flat equalizer.
+    fn default() -> Self {
+        Equalizer {
+            is_enabled: false,
+            preset: Preset::Flat,
+            bands: [0.0; BANDS],
+        }
+    }
+}
+
+impl Equalizer {
+    /// Choose the preset, and set the bands to its gains.
+    pub fn set_preset(&mut self, preset: Preset) {
+        self.preset = preset;
+        if let Some(bands) = preset.bands() {
+            self.bands = bands;
+        }
+    }
+
+    /// Return the VLC arguments that apply the equalizer, or none if it is
+    /// disabled.
+    pub fn vlc_args(&self) -> Vec<String> {
+        if !self.is_enabled {
+            return Vec::new();
+        }
+        let bands: Vec<String> = self.bands.iter().map(|gain| gain.to_string()).collect();
+        let boost = self.bands.iter().copied().fold(0.0, f32::max);
+        vec![
+            "--audio-filter=equalizer".to_owned(),
+            format!("--equalizer-bands={}", bands.join(" ")),
+            format!("--equalizer-preamp={}", PREAMP - boost),
+        ]
+    }
+}
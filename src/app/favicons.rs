@@ -0,0 +1,151 @@
+#[cfg(not(target_arch = "wasm32"))]
+use super::disk_cache;
+use eframe::egui;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// The size of the station logos shown in the grid.
+const FAVICON_SIZE: f32 = 24.0;
+
+/// The largest size, in pixels, the logos are decoded at. Larger logos are
+/// scaled down to save memory.
+const DECODED_SIZE: u32 = 48;
+
+/// The state of a station logo.
+enum Favicon {
+    /// The logo is downloading or decoding. The result is stored when done.
+    Loading(Arc<Mutex<Option<Result<egui::ColorImage, String>>>>),
+    /// The logo is ready to be shown.
+    Loaded(egui::TextureHandle),
+    /// The logo could not be downloaded or decoded.
+    Failed,
+}
+
+/// Downloads the station logos when they are first shown, and keeps them, so
+/// each logo is only downloaded once.
+#[derive(Default)]
+pub struct FaviconCache {
+    /// The logos by URL.
+    favicons: HashMap<String, Favicon>,
+}
+
+impl FaviconCache {
+    /// Show the station logo at the given URL. A radio icon is shown while the
+    /// logo is downloading, or if there is no logo. Only the logos that are
+    /// visible are downloaded.
+    pub fn show(&mut self, ui: &mut egui::Ui, url: &str) {
+        let (rect, _) =
+            ui.allocate_exact_size(egui::Vec2::splat(FAVICON_SIZE), egui::Sense::hover());
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
+
+        match self.texture(ui.ctx(), url.trim()) {
+            Some(texture) => {
+                // Fit the logo in the square, keeping its aspect ratio.
+                let size = texture.size_vec2();
+                let size = size * (FAVICON_SIZE / size.max_elem());
+                let rect = egui::Rect::from_center_size(rect.center(), size);
+                egui::Image::new(texture.id(), size).paint_at(ui, rect);
+            }
+            None => {
+                ui.painter().text(
+                    rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    "📻",
+                    egui::TextStyle::Body.resolve(ui.style()),
+                    ui.visuals().text_color(),
+                );
+            }
+        }
+    }
+
+    /// Return the logo at the given URL, if it is ready. Start downloading it
+    /// if it was not requested yet.
+    fn texture(&mut self, ctx: &egui::Context, url: &str) -> Option<&egui::TextureHandle> {
+        // Stations without a logo have no URL.
+        if url.is_empty() {
+            return None;
+        }
+
+        let favicon = self
+            .favicons
+            .entry(url.to_owned())
+            .or_insert_with(|| Favicon::Loading(fetch_favicon(ctx, url)));
+
+        // Create the texture once the logo is decoded.
+        if let Favicon::Loading(result) = favicon {
+            let result = result.lock().unwrap().take();
+            match result {
+                Some(Ok(image)) => *favicon = Favicon::Loaded(ctx.load_texture(url, image)),
+                Some(Err(_)) => *favicon = Favicon::Failed,
+                None => {}
+            }
+        }
+
+        match favicon {
+            Favicon::Loaded(texture) => Some(texture),
+            Favicon::Loading(_) | Favicon::Failed => None,
+        }
+    }
+}
+
+/// How long the downloaded logos are reused natively, instead of downloading
+/// them again.
+#[cfg(not(target_arch = "wasm32"))]
+const FAVICON_CACHE_AGE: std::time::Duration = std::time::Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Download and decode the logo in the background. The result is stored once
+/// done, and the user interface is repainted to show it. Natively, the logos
+/// are stored on the disk, and only downloaded if not stored.
+fn fetch_favicon(
+    ctx: &egui::Context,
+    url: &str,
+) -> Arc<Mutex<Option<Result<egui::ColorImage, String>>>> {
+    let result = Arc::new(Mutex::new(None));
+    let result_store = result.clone();
+    let ctx = ctx.clone();
+
+    // Decode the stored logo in the background, since logos may be large.
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(bytes) = disk_cache::read("favicons", url, FAVICON_CACHE_AGE) {
+        std::thread::spawn(move || {
+            *result_store.lock().unwrap() = Some(decode_favicon(&bytes));
+            ctx.request_repaint();
+        });
+        return result;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let url_store = url.to_owned();
+    ehttp::fetch(ehttp::Request::get(url), move |response| {
+        let image = response.and_then(|response| {
+            if response.ok {
+                #[cfg(not(target_arch = "wasm32"))]
+                disk_cache::write("favicons", &url_store, &response.bytes);
+                decode_favicon(&response.bytes)
+            } else {
+                Err(format!("{} {}", response.status, response.status_text))
+            }
+        });
+        *result_store.lock().unwrap() = Some(image);
+        ctx.request_repaint();
+    });
+    result
+}
+
+/// Decode the logo, scaling it down if it is large.
+fn decode_favicon(bytes: &[u8]) -> Result<egui::ColorImage, String> {
+    let image = image::load_from_memory(bytes).map_err(|err| err.to_string())?;
+    let image = if image.width() > DECODED_SIZE || image.height() > DECODED_SIZE {
+        image.thumbnail(DECODED_SIZE, DECODED_SIZE)
+    } else {
+        image
+    };
+    let image = image.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_raw(),
+    ))
+}
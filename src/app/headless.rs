@@ -0,0 +1,55 @@
+use super::player::{PlaybackState, Player};
+use super::vlc_media_player::VLCMediaPlayer;
+use super::volume::DEFAULT_VOLUME;
+use std::thread;
+use std::time::Duration;
+
+/// How often the player is asked what it is doing.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Play the stream URL with VLC, without the application window, at the given
+/// volume or at the default one. Print what the player does, such as the
+/// track titles, and block until the process is interrupted, such as with
+/// Ctrl-C. Return why the stream could not be played, if it could not, such
+/// as a volume out of range.
+pub fn play_headless(url: &str, volume: Option<i32>) -> Result<(), String> {
+    let mut media_player = VLCMediaPlayer::new(DEFAULT_VOLUME);
+    if let Some(reason) = media_player.unavailable_reason() {
+        return Err(reason);
+    }
+    if let Some(level) = volume {
+        media_player
+            .try_set_volume(level)
+            .map_err(|err| err.to_string())?;
+    }
+    media_player.set_src(url);
+    media_player.play();
+
+    // Print the state and the track title whenever they change.
+    let mut shown_state = PlaybackState::Idle;
+    let mut shown_title = None;
+    loop {
+        media_player.tick();
+        let state = media_player.state();
+        if state != shown_state {
+            match &state {
+                PlaybackState::Connecting => eprintln!("Connecting…"),
+                PlaybackState::Playing => eprintln!("Playing {}", url),
+                PlaybackState::Reconnecting => eprintln!("Reconnecting…"),
+                PlaybackState::Error(reason) => return Err(reason.clone()),
+                PlaybackState::Idle => {}
+            }
+            shown_state = state;
+        }
+
+        let title = media_player.track_title();
+        if title != shown_title {
+            if let Some(title) = &title {
+                println!("{}", title);
+            }
+            shown_title = title;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::radio_browser::{handle_response, station_by_uuid_request, Station};
+use eframe::egui;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Whether the radio browser was asked for the current streams of the
+/// playing station.
+#[derive(Default)]
+enum Requery {
+    /// The station was not listed again.
+    #[default]
+    NotAsked,
+    /// The station was asked for, and is listed here once answered.
+    Asked(Arc<Mutex<Option<Vec<Station>>>>),
+    /// The listed streams were added.
+    Answered,
+}
+
+/// What to do when the playing stream fails.
+#[derive(Debug, PartialEq)]
+pub enum Failover {
+    /// Play the stream with the given URL instead.
+    Retry(String),
+    /// Wait for the radio browser to list the streams of the station again.
+    Waiting,
+    /// Every stream of the station failed.
+    GiveUp,
+}
+
+/// The streams of the playing station: its own, and those of the other
+/// stations with the same name, such as at another bitrate or on another
+/// server. When the playing stream fails, the next one is tried.
+#[derive(Default)]
+pub struct Mirrors {
+    /// The streams of the station, the highest bitrate first.
+    streams: Vec<Station>,
+
+    /// The URLs of the streams that failed since the station was chosen.
+    failed: HashSet<String>,
+
+    /// Whether the radio browser was asked for the current streams.
+    requery: Requery,
+}
+
+impl Mirrors {
+    /// Return the streams of the station, which are the given stations with
+    /// the same name, ignoring case, and the station itself.
+    pub fn new(station: &Station, stations: &[Station]) -> Self {
+        let name = station.name.trim().to_lowercase();
+        let mut mirrors = Mirrors::default();
+        mirrors.add(
+            std::iter::once(station).chain(
+                stations
+                    .iter()
+                    .filter(|other| other.name.trim().to_lowercase() == name),
+            ),
+        );
+        mirrors
+    }
+
+    /// Add the streams that are not known yet, keeping the highest bitrate
+    /// first. The sort is stable, so the streams of the same bitrate keep
+    /// the order they were found in.
+    fn add<'a>(&mut self, stations: impl IntoIterator<Item = &'a Station>) {
+        for station in stations {
+            let url = station.stream_url();
+            if !url.is_empty() && !self.streams.iter().any(|known| known.stream_url() == url) {
+                self.streams.push(station.clone());
+            }
+        }
+        self.streams.sort_by_key(|station| Reverse(station.bitrate));
+    }
+
+    /// Return the streams of the station, the highest bitrate first.
+    pub fn streams(&self) -> &[Station] {
+        &self.streams
+    }
+
+    /// Return the stream with the given URL, if it is a stream of the
+    /// station.
+    pub fn stream(&self, url: &str) -> Option<&Station> {
+        self.streams
+            .iter()
+            .find(|station| station.stream_url() == url)
+    }
+
+    /// Mark the stream as failed, and return what to do next: play another
+    /// stream that has not failed, or else ask the radio browser once for
+    /// the current streams of the station with the given UUID, if any, or
+    /// give up.
+    pub fn failover(
+        &mut self,
+        ctx: &egui::Context,
+        failed_url: &str,
+        uuid: Option<&str>,
+    ) -> Failover {
+        self.failed.insert(failed_url.to_owned());
+
+        // Add the streams listed by the radio browser, once it answers.
+        if let Requery::Asked(listed) = &self.requery {
+            let listed = listed.lock().unwrap().take();
+            if let Some(listed) = listed {
+                self.add(&listed);
+                self.requery = Requery::Answered;
+            }
+        }
+
+        if let Some(station) = self
+            .streams
+            .iter()
+            .find(|station| !self.failed.contains(station.stream_url()))
+        {
+            return Failover::Retry(station.stream_url().to_owned());
+        }
+        match (&self.requery, uuid) {
+            (Requery::NotAsked, Some(uuid)) => {
+                self.requery = Requery::Asked(ask_streams(ctx, uuid));
+                Failover::Waiting
+            }
+            (Requery::Asked(_), _) => Failover::Waiting,
+            _ => Failover::GiveUp,
+        }
+    }
+}
+
+/// Ask the radio browser for the station with the given UUID, whose streams
+/// may have changed since it was listed. A failed request lists nothing.
+fn ask_streams(ctx: &egui::Context, uuid: &str) -> Arc<Mutex<Option<Vec<Station>>>> {
+    let listed = Arc::new(Mutex::new(None));
+    let answer = listed.clone();
+    let ctx = ctx.clone();
+    ehttp::fetch(station_by_uuid_request(uuid), move |response| {
+        let stations = handle_response(response).unwrap_or_else(|err| {
+            log::warn!("Could not list the streams of the station again: {}", err);
+            Vec::new()
+        });
+        *answer.lock().unwrap() = Some(stations);
+        ctx.request_repaint();
+    });
+    listed
+}
+
+/// Return the text that tells the stream apart from the other streams of the
+/// station, such as "128 kbps MP3".
+pub fn stream_label(station: &Station) -> String {
+    match (station.bitrate, station.codec.trim()) {
+        (0, "") => "?".to_owned(),
+        (0, codec) => codec.to_owned(),
+        (bitrate, codec) => format!("{} kbps {}", bitrate, codec).trim().to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(name: &str, url: &str, bitrate: u32) -> Station {
+        Station {
+            name: name.to_owned(),
+            url: url.to_owned(),
+            bitrate,
+            ..Station::default()
+        }
+    }
+
+    fn urls(mirrors: &Mirrors) -> Vec<&str> {
+        mirrors.streams().iter().map(Station::stream_url).collect()
+    }
+
+    #[test]
+    fn stations_with_the_same_name_are_streams_of_the_station() {
+        let playing = stream("Jazz FM", "http://a/64", 64);
+        let stations = vec![
+            stream("jazz fm ", "http://a/128", 128),
+            stream("Rock FM", "http://b/320", 320),
+            stream("Jazz FM", "http://a/64", 64),
+            stream("Jazz FM", "http://c/128", 128),
+        ];
+        let mirrors = Mirrors::new(&playing, &stations);
+        assert_eq!(
+            urls(&mirrors),
+            ["http://a/128", "http://c/128", "http://a/64"]
+        );
+    }
+
+    #[test]
+    fn failed_streams_are_not_tried_again() {
+        let ctx = egui::Context::default();
+        let playing = stream("Jazz FM", "http://a/128", 128);
+        let stations = vec![stream("Jazz FM", "http://b/64", 64)];
+        let mut mirrors = Mirrors::new(&playing, &stations);
+        assert_eq!(
+            mirrors.failover(&ctx, "http://a/128", None),
+            Failover::Retry("http://b/64".to_owned())
+        );
+        assert_eq!(
+            mirrors.failover(&ctx, "http://b/64", None),
+            Failover::GiveUp
+        );
+    }
+
+    #[test]
+    fn listed_streams_are_tried_once_answered() {
+        let ctx = egui::Context::default();
+        let playing = stream("Jazz FM", "http://a/128", 128);
+        let mut mirrors = Mirrors::new(&playing, &[]);
+        let listed = Arc::new(Mutex::new(None));
+        mirrors.requery = Requery::Asked(listed.clone());
+        assert_eq!(
+            mirrors.failover(&ctx, "http://a/128", Some("uuid")),
+            Failover::Waiting
+        );
+
+        *listed.lock().unwrap() = Some(vec![stream("Jazz FM", "http://new/128", 128)]);
+        assert_eq!(
+            mirrors.failover(&ctx, "http://a/128", Some("uuid")),
+            Failover::Retry("http://new/128".to_owned())
+        );
+        assert_eq!(
+            mirrors.failover(&ctx, "http://new/128", Some("uuid")),
+            Failover::GiveUp
+        );
+    }
+
+    #[test]
+    fn streams_are_told_apart_by_quality() {
+        let mut station = stream("Jazz FM", "http://a/128", 128);
+        station.codec = "MP3".to_owned();
+        assert_eq!(stream_label(&station), "128 kbps MP3");
+        station.bitrate = 0;
+        assert_eq!(stream_label(&station), "MP3");
+        station.codec.clear();
+        assert_eq!(stream_label(&station), "?");
+    }
+}
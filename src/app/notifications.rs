@@ -0,0 +1,101 @@
+use eframe::egui;
+use std::sync::{Arc, Mutex};
+
+/// How long a notification is shown, in seconds.
+const NOTIFICATION_DURATION: f64 = 5.0;
+
+/// How important a notification is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    /// Something the user may want to know, such as the result of an action.
+    Info,
+    /// Something that went wrong.
+    Error,
+}
+
+/// A message shown to the user for a few seconds.
+struct Notification {
+    /// The message.
+    text: String,
+    /// How important the message is.
+    severity: Severity,
+    /// When the message is hidden, in seconds, once it was first shown.
+    expires_at: Option<f64>,
+}
+
+/// The messages shown to the user in the bottom right corner, the oldest
+/// first. Clones share the messages, so background requests can add messages
+/// too.
+#[derive(Clone, Default)]
+pub struct Notifications {
+    /// The messages that are not hidden yet.
+    queue: Arc<Mutex<Vec<Notification>>>,
+}
+
+impl Notifications {
+    /// Add a message that informs the user.
+    pub fn info(&self, text: impl Into<String>) {
+        self.push(Severity::Info, text.into());
+    }
+
+    /// Add a message that tells the user something went wrong.
+    pub fn error(&self, text: impl Into<String>) {
+        self.push(Severity::Error, text.into());
+    }
+
+    /// Add a message, unless the same message is already shown, so a
+    /// repeated failure does not pile up.
+    fn push(&self, severity: Severity, text: String) {
+        let mut queue = self.queue.lock().unwrap();
+        if !queue.iter().any(|shown| shown.text == text) {
+            queue.push(Notification {
+                text,
+                severity,
+                expires_at: None,
+            });
+        }
+    }
+
+    /// Show the messages stacked in the bottom right corner, each with a
+    /// button that hides it. The messages are hidden a few seconds after they
+    /// are first shown.
+    pub fn show(&self, ctx: &egui::Context) {
+        let now = ctx.input().time;
+        let mut queue = self.queue.lock().unwrap();
+        queue.retain(|notification| notification.expires_at.is_none_or(|at| now < at));
+        if queue.is_empty() {
+            return;
+        }
+
+        // Keep repainting until every message is hidden.
+        ctx.request_repaint();
+
+        egui::Area::new("notifications")
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -48.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let mut dismissed = None;
+                for (index, notification) in queue.iter_mut().enumerate() {
+                    notification
+                        .expires_at
+                        .get_or_insert(now + NOTIFICATION_DURATION);
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            match notification.severity {
+                                Severity::Info => ui.label(&notification.text),
+                                Severity::Error => {
+                                    ui.colored_label(egui::Color32::RED, &notification.text)
+                                }
+                            };
+                            if ui.small_button("✖").clicked() {
+                                dismissed = Some(index);
+                            }
+                        });
+                    });
+                }
+                if let Some(index) = dismissed {
+                    queue.remove(index);
+                }
+            });
+    }
+}
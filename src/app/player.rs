@@ -0,0 +1,390 @@
+use super::volume::{validate_volume, VolumeError, MAX_BOOSTED_VOLUME};
+use eframe::egui;
+#[cfg(target_arch = "wasm32")]
+use eframe::wasm_bindgen::{prelude::*, JsCast};
+#[cfg(target_arch = "wasm32")]
+use std::cell::{Cell, RefCell};
+#[cfg(target_arch = "wasm32")]
+use std::rc::Rc;
+
+/// How often, in seconds, the title of the playing track is asked for by
+/// default.
+pub const DEFAULT_METADATA_INTERVAL: u32 = 5;
+
+/// The shortest and longest time, in seconds, between the requests for the
+/// title of the playing track.
+pub const METADATA_INTERVAL_RANGE: std::ops::RangeInclusive<u32> = 1..=60;
+
+/// What the media player is doing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaybackState {
+    /// Nothing is playing.
+    Idle,
+    /// The station is connecting or buffering, and cannot be heard yet.
+    Connecting,
+    /// The station can be heard.
+    Playing,
+    /// The station stopped on its own, and is being connected again.
+    Reconnecting,
+    /// The station could not be played, for the given reason.
+    Error(String),
+}
+
+/// An audio output device the media player can play through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioDevice {
+    /// The id that selects the device. The default device has an empty id.
+    pub id: String,
+    /// The name shown to the user.
+    pub name: String,
+}
+
+/// The methods shared by the media players of every platform, so the app can
+/// play stations without caring which platform it runs on.
+pub trait Player {
+    /// Set the URL of the station to play with play().
+    fn set_src(&mut self, url: &str);
+
+    /// Start playing the station set with set_src(), replacing the playing
+    /// station, if any. Does nothing if no station was set.
+    fn play(&mut self);
+
+    /// Stop playing the station. The station set with set_src() is kept, so
+    /// play() plays it again.
+    fn pause(&mut self);
+
+    /// Set the volume level, from 0 to 100, or above 100 to amplify quiet
+    /// stations if the player can. Levels out of range are clamped.
+    fn set_volume(&mut self, volume: i32);
+
+    /// Set the volume level like set_volume(), but return an error instead
+    /// of clamping a level the player cannot play at.
+    fn try_set_volume(&mut self, volume: i32) -> Result<(), VolumeError> {
+        self.set_volume(validate_volume(volume, MAX_BOOSTED_VOLUME)?);
+        Ok(())
+    }
+
+    /// Return what the media player is doing.
+    fn state(&self) -> PlaybackState;
+
+    /// Check on the playing station, such as to connect a dropped station
+    /// again. Called once per frame, before the state is read.
+    fn tick(&mut self) {}
+
+    /// Return the title of the playing track, such as "Artist - Song", if
+    /// the station sends it. Browsers do not pass the stream metadata to the
+    /// page, so only native players know it.
+    fn track_title(&self) -> Option<String> {
+        None
+    }
+
+    /// Return the bitrate of the playing stream in kbps, as measured while
+    /// playing, if the player can measure it.
+    fn measured_bitrate(&self) -> Option<u32> {
+        None
+    }
+
+    /// Give the player the user interface to repaint when what it reports
+    /// changes on its own, such as the track title.
+    fn set_repaint_context(&mut self, _ctx: &egui::Context) {}
+
+    /// Return why the player cannot play at all, such as a missing media
+    /// library, or None if it can play.
+    fn unavailable_reason(&self) -> Option<String> {
+        None
+    }
+
+    /// Set whether playing another station fades out the playing one as the
+    /// new one fades in. Players that cannot fade cut from one station to the
+    /// next.
+    fn set_crossfade(&mut self, _enabled: bool) {}
+
+    /// Equalize the stations with the given equalizer. Players that cannot
+    /// equalize play the stations as they are.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn set_equalizer(&mut self, _equalizer: &super::equalizer::Equalizer) {}
+
+    /// Set whether the stations start silent and ease in to the volume, so a
+    /// loud station does not blast. Players that cannot fade start at the
+    /// volume.
+    fn set_fade_in(&mut self, _enabled: bool) {}
+
+    /// Set how often, in seconds, the player asks for the title of the
+    /// playing track. Players that cannot tell the title ignore it.
+    fn set_metadata_interval(&mut self, _seconds: u32) {}
+
+    /// Return the audio devices the player can play through, which may only
+    /// be known once a station plays. Players that cannot choose the device
+    /// return none.
+    fn audio_devices(&self) -> Vec<AudioDevice> {
+        Vec::new()
+    }
+
+    /// Play through the audio device with the given id, or through the
+    /// default device if the id is empty.
+    fn set_audio_device(&mut self, _id: &str) {}
+
+    /// Stop playing, and release the audio device, such as when the
+    /// application quits. The player may not play afterwards.
+    fn shutdown(&mut self) {
+        self.pause();
+    }
+
+    /// Return whether the station is playing, connecting, or reconnecting.
+    fn is_playing(&self) -> bool {
+        matches!(
+            self.state(),
+            PlaybackState::Connecting | PlaybackState::Playing | PlaybackState::Reconnecting
+        )
+    }
+}
+
+/// The media player used on the web.
+#[cfg(target_arch = "wasm32")]
+pub type PlatformPlayer = WebPlayer;
+
+/// The media player used natively.
+#[cfg(not(target_arch = "wasm32"))]
+pub type PlatformPlayer = super::vlc_media_player::VLCMediaPlayer;
+
+/// How long the web stream may wait for data before it is connected again,
+/// in milliseconds.
+#[cfg(target_arch = "wasm32")]
+const STALL_TIMEOUT: f64 = 10_000.0;
+
+/// What the events of the audio element tell about the station, shared
+/// between the web player and its event listeners.
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+struct WebShared {
+    /// Whether the station was heard since it was set or stopped. Until then,
+    /// waiting for data is connecting, not a drop.
+    played: Cell<bool>,
+
+    /// When the stream started waiting for data after it played, in
+    /// milliseconds since the Unix epoch, while it waits.
+    stalled_since: Cell<Option<f64>>,
+
+    /// Whether the station is being connected again.
+    reconnecting: Cell<bool>,
+
+    /// Whether the station was connected again since it last played, since
+    /// it is only connected again once.
+    reconnected: Cell<bool>,
+
+    /// Whether the station dropped again after it was connected again, so
+    /// connecting it is given up.
+    gave_up: Cell<bool>,
+
+    /// The user interface to repaint when the events change the state.
+    repaint: RefCell<Option<egui::Context>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl WebShared {
+    /// Repaint the user interface, if known, to show the new state.
+    fn repaint(&self) {
+        if let Some(ctx) = &*self.repaint.borrow() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Connect the station again by setting its URL again, unless it was
+    /// already connected again since it last played. Return whether it was
+    /// connected again.
+    fn reconnect(&self, audio: &web_sys::HtmlAudioElement) -> bool {
+        if self.reconnected.replace(true) {
+            return false;
+        }
+        self.reconnecting.set(true);
+        self.stalled_since.set(None);
+        audio.set_src(&audio.src());
+        let _ = audio.play();
+        true
+    }
+
+    /// Remember that the station can be heard, so a later drop may be
+    /// connected again.
+    fn playing(&self) {
+        self.played.set(true);
+        self.stalled_since.set(None);
+        self.reconnecting.set(false);
+        self.reconnected.set(false);
+        self.gave_up.set(false);
+    }
+
+    /// Forget the station, its stalls, and reconnections, such as when the
+    /// user plays or stops a station.
+    fn clear(&self) {
+        self.playing();
+        self.played.set(false);
+    }
+}
+
+/// Plays stations on the web with an audio element.
+#[cfg(target_arch = "wasm32")]
+pub struct WebPlayer {
+    /// The Web-sys audio element that plays the stations.
+    audio: web_sys::HtmlAudioElement,
+
+    /// Why the station cannot be played, if the browser would refuse it.
+    blocked: Option<String>,
+
+    /// What the events of the audio element tell about the station.
+    shared: Rc<WebShared>,
+
+    /// The listeners of the audio element events, which are kept so the
+    /// browser can call them as long as the player exists.
+    _listeners: Vec<Closure<dyn FnMut()>>,
+}
+
+/// Implement trait to create the default web player.
+#[cfg(target_arch = "wasm32")]
+impl Default for WebPlayer {
+    /// Create a player with a new audio element, which connects a dropped
+    /// station again once.
+    fn default() -> Self {
+        let audio = web_sys::HtmlAudioElement::new().unwrap();
+        let shared = Rc::new(WebShared::default());
+        let mut listeners = Vec::new();
+
+        // The stream waits for data, such as while buffering, or because the
+        // data stopped arriving. Waiting before the station played is part
+        // of connecting, not a drop.
+        for event in ["waiting", "stalled"] {
+            let shared = shared.clone();
+            listeners.push(listen(&audio, event, move || {
+                if shared.played.get() && shared.stalled_since.get().is_none() {
+                    shared.stalled_since.set(Some(js_sys::Date::now()));
+                }
+                shared.repaint();
+            }));
+        }
+
+        // The station can be heard, so a later drop may be connected again.
+        let playing_shared = shared.clone();
+        listeners.push(listen(&audio, "playing", move || {
+            playing_shared.playing();
+            playing_shared.repaint();
+        }));
+
+        // The stream failed, so connect it again, once.
+        let error_shared = shared.clone();
+        let error_audio = audio.clone();
+        listeners.push(listen(&audio, "error", move || {
+            if !error_shared.reconnect(&error_audio) {
+                error_shared.reconnecting.set(false);
+            }
+            error_shared.repaint();
+        }));
+
+        WebPlayer {
+            audio,
+            blocked: None,
+            shared,
+            _listeners: listeners,
+        }
+    }
+}
+
+/// Call the function whenever the audio element sends the event. The returned
+/// listener must be kept, or the browser cannot call it.
+#[cfg(target_arch = "wasm32")]
+fn listen(
+    audio: &web_sys::HtmlAudioElement,
+    event: &str,
+    function: impl FnMut() + 'static,
+) -> Closure<dyn FnMut()> {
+    let listener = Closure::wrap(Box::new(function) as Box<dyn FnMut()>);
+    let _ = audio.add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+    listener
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Player for WebPlayer {
+    fn set_src(&mut self, url: &str) {
+        self.blocked = is_mixed_content(url)
+            .then(|| "This station uses insecure HTTP and can't play in the browser.".to_owned());
+        self.shared.clear();
+        self.audio.set_src(url);
+    }
+
+    fn play(&mut self) {
+        // Browsers silently refuse the stations they block, so do not try.
+        if self.blocked.is_none() {
+            let _ = self.audio.play();
+        }
+    }
+
+    fn pause(&mut self) {
+        self.shared.clear();
+        let _ = self.audio.pause();
+    }
+
+    fn set_repaint_context(&mut self, ctx: &egui::Context) {
+        *self.shared.repaint.borrow_mut() = Some(ctx.clone());
+    }
+
+    fn set_volume(&mut self, volume: i32) {
+        // Web-sys takes volme as a float in the range 0.0 to 1.0, and throws
+        // outside of it, so clamp the volume first. Browsers cannot amplify
+        // the audio, so a boosted volume plays at 100%.
+        let volume = volume.clamp(0, 100);
+        self.audio.set_volume(volume as f64 / 100.0);
+    }
+
+    fn try_set_volume(&mut self, volume: i32) -> Result<(), VolumeError> {
+        // Browsers cannot amplify the audio.
+        self.set_volume(validate_volume(volume, 100)?);
+        Ok(())
+    }
+
+    fn tick(&mut self) {
+        // Connect the station again if it waited too long for data, once. If
+        // it still waits after that, give up.
+        let stalled_for = self
+            .shared
+            .stalled_since
+            .get()
+            .map(|since| js_sys::Date::now() - since);
+        if !self.audio.paused()
+            && stalled_for.is_some_and(|stalled_for| stalled_for >= STALL_TIMEOUT)
+            && !self.shared.reconnect(&self.audio)
+        {
+            self.shared.gave_up.set(true);
+        }
+    }
+
+    fn state(&self) -> PlaybackState {
+        // The ready state from which the media can be played without waiting.
+        const HAVE_FUTURE_DATA: u16 = 3;
+
+        if let Some(reason) = &self.blocked {
+            PlaybackState::Error(reason.clone())
+        } else if self.shared.gave_up.get() {
+            PlaybackState::Error("Could not reconnect to station.".to_owned())
+        } else if self.shared.reconnecting.get() {
+            PlaybackState::Reconnecting
+        } else if let Some(error) = self.audio.error() {
+            PlaybackState::Error(format!("Could not play station (error {}).", error.code()))
+        } else if self.audio.paused() {
+            PlaybackState::Idle
+        } else if self.audio.ready_state() < HAVE_FUTURE_DATA
+            || self.shared.stalled_since.get().is_some()
+        {
+            PlaybackState::Connecting
+        } else {
+            PlaybackState::Playing
+        }
+    }
+}
+
+/// Return whether the browser blocks the URL because it is insecure, which is
+/// the case for HTTP URLs when the page itself was loaded with HTTPS.
+#[cfg(target_arch = "wasm32")]
+fn is_mixed_content(url: &str) -> bool {
+    let page_is_secure = web_sys::window()
+        .and_then(|window| window.location().protocol().ok())
+        .is_some_and(|protocol| protocol == "https:");
+    page_is_secure && url.trim_start().to_ascii_lowercase().starts_with("http:")
+}
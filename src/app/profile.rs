@@ -0,0 +1,115 @@
+#[cfg(not(target_arch = "wasm32"))]
+use super::equalizer::{Equalizer, Preset};
+use crate::radio_browser::Station;
+use std::collections::HashMap;
+
+/// The lowest and highest volume offset of a station, in volume levels.
+pub const VOLUME_OFFSET_RANGE: std::ops::RangeInclusive<i32> = -50..=50;
+
+/// How a station is played, which is applied whenever it starts playing,
+/// such as a quiet station played louder. The profiles are keyed by station
+/// UUID.
+#[derive(Debug, Default, Clone, PartialEq)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+/// New fields are given default values when deserializing old state.
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct StationProfile {
+    /// Added to the volume while the station plays. The slider keeps showing
+    /// the volume chosen by the user.
+    pub volume_offset: i32,
+
+    /// The equalizer preset of the station, or None to use the equalizer of
+    /// the options.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub equalizer: Option<Preset>,
+}
+
+impl StationProfile {
+    /// Return whether the profile changes nothing, so it need not be kept.
+    pub fn is_neutral(&self) -> bool {
+        *self == StationProfile::default()
+    }
+}
+
+/// Return the profile of the station, if it has one.
+pub fn profile_of<'a>(
+    profiles: &'a HashMap<String, StationProfile>,
+    station: Option<&Station>,
+) -> Option<&'a StationProfile> {
+    station.and_then(|station| profiles.get(&station.stationuuid))
+}
+
+/// Return the volume level to play at, which is the level of the slider moved
+/// by the volume offset of the profile, if any, up to the highest level. A
+/// silent volume stays silent.
+pub fn profile_volume(level: i32, profile: Option<&StationProfile>, max: i32) -> i32 {
+    let offset = profile.map_or(0, |profile| profile.volume_offset);
+    if level == 0 {
+        0
+    } else {
+        (level + offset).clamp(0, max)
+    }
+}
+
+/// Return the equalizer to play with, which is the preset of the profile, if
+/// any, or else the equalizer of the options.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn profile_equalizer(equalizer: &Equalizer, profile: Option<&StationProfile>) -> Equalizer {
+    match profile.and_then(|profile| profile.equalizer) {
+        Some(preset) => {
+            let mut equalizer = Equalizer {
+                is_enabled: true,
+                ..Equalizer::default()
+            };
+            equalizer.set_preset(preset);
+            equalizer
+        }
+        None => equalizer.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn louder(offset: i32) -> StationProfile {
+        StationProfile {
+            volume_offset: offset,
+            ..StationProfile::default()
+        }
+    }
+
+    #[test]
+    fn volume_offset_moves_the_level_within_range() {
+        assert_eq!(profile_volume(50, None, 100), 50);
+        assert_eq!(profile_volume(50, Some(&louder(20)), 100), 70);
+        assert_eq!(profile_volume(90, Some(&louder(20)), 100), 100);
+        assert_eq!(profile_volume(10, Some(&louder(-20)), 100), 0);
+    }
+
+    #[test]
+    fn silent_volume_stays_silent() {
+        assert_eq!(profile_volume(0, Some(&louder(20)), 100), 0);
+    }
+
+    #[test]
+    fn only_changed_profiles_are_kept() {
+        assert!(StationProfile::default().is_neutral());
+        assert!(!louder(5).is_neutral());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn preset_of_the_profile_replaces_the_equalizer() {
+        let options = Equalizer::default();
+        let jazz = StationProfile {
+            equalizer: Some(Preset::Jazz),
+            ..StationProfile::default()
+        };
+        let equalizer = profile_equalizer(&options, Some(&jazz));
+        assert!(equalizer.is_enabled);
+        assert_eq!(equalizer.preset, Preset::Jazz);
+        assert_eq!(profile_equalizer(&options, Some(&louder(5))), options);
+    }
+}
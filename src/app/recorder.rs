@@ -0,0 +1,168 @@
+use super::vlc_media_player::quit_vlc;
+use crate::radio_browser::Station;
+use chrono::DateTime;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+/// Records the playing station to a file with a second VLC, which saves the
+/// stream as it arrives, without decoding it. Only one station is recorded at
+/// a time.
+#[derive(Default)]
+pub struct Recorder {
+    /// The VLC that records, while recording.
+    process: Option<(Child, ChildStdin)>,
+
+    /// The URL of the recorded station, while recording.
+    url: String,
+
+    /// The file the station is recorded to, while recording.
+    path: Option<PathBuf>,
+}
+
+impl Recorder {
+    /// Start recording the station to a new file in the given directory,
+    /// named after the station and the current time, and stop recording the
+    /// previous station, if any. Return the path of the file.
+    pub fn start(&mut self, station: &Station, directory: &Path, time: i64) -> io::Result<PathBuf> {
+        self.stop();
+
+        // Create the file first, so a directory that is not writable is
+        // reported right away, instead of VLC failing silently.
+        std::fs::create_dir_all(directory)?;
+        let path = directory.join(recording_file_name(station, time));
+        File::create(&path)?;
+
+        // Save the stream as is, since decoding and encoding it again would
+        // lower its quality.
+        let url = station.stream_url();
+        let mut command = Command::new("vlc");
+        command.args(["--intf", "rc", "--no-video", "--quiet"]);
+        // The remote control interface only reads commands from a terminal,
+        // unless told otherwise.
+        #[cfg(not(target_os = "windows"))]
+        command.arg("--rc-fake-tty");
+        // Do not open a console window for the remote control interface.
+        #[cfg(target_os = "windows")]
+        command.arg("--rc-quiet");
+        command.arg(format!(
+            "--sout=#std{{access=file,mux=raw,dst=\"{}\"}}",
+            path.display()
+        ));
+        command.arg(url);
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        // The standard input is always piped, as requested above.
+        let stdin = child.stdin.take().expect("VLC standard input is piped");
+
+        self.process = Some((child, stdin));
+        self.url = url.to_owned();
+        self.path = Some(path.clone());
+        Ok(path)
+    }
+
+    /// Stop recording, and let VLC finish writing the file.
+    pub fn stop(&mut self) {
+        if let Some((child, stdin)) = self.process.take() {
+            quit_vlc(child, stdin);
+        }
+        self.url.clear();
+        self.path = None;
+    }
+
+    /// Return the URL of the recorded station, while recording.
+    pub fn url(&self) -> Option<&str> {
+        self.process.as_ref().map(|_| self.url.as_str())
+    }
+
+    /// Return the file the station is recorded to, while recording.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+/// Stop recording when the application quits.
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Return the directory the recordings are saved to when the user did not
+/// choose one, which is the music directory of the user, if known.
+pub fn default_directory() -> Option<PathBuf> {
+    let dirs = directories_next::UserDirs::new()?;
+    Some(
+        dirs.audio_dir()
+            .unwrap_or_else(|| dirs.home_dir())
+            .join("Radio recordings"),
+    )
+}
+
+/// Return the name of the file a station is recorded to, such as
+/// "Jazz FM 2022-08-06 12-34-56.mp3". The characters that file systems
+/// reserve are removed from the station name, and the extension follows the
+/// codec, since the stream is saved as is.
+fn recording_file_name(station: &Station, time: i64) -> String {
+    let name: String = station
+        .name
+        .chars()
+        .filter(|c| {
+            !c.is_control() && !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        })
+        .collect();
+    let name = match name.trim() {
+        "" => "Station",
+        name => name,
+    };
+    let extension = match station.codec.trim().to_lowercase().as_str() {
+        "mp3" => "mp3",
+        "aac" | "aac+" | "aacp" | "he-aac" => "aac",
+        "ogg" | "vorbis" => "ogg",
+        "opus" => "opus",
+        "flac" => "flac",
+        _ => "dat",
+    };
+    format!("{} {}.{}", name, format_time(time), extension)
+}
+
+/// Format seconds since the Unix epoch as "2022-08-06 12-34-56", in UTC, which
+/// sorts by time and has no characters file systems reserve. Times too far
+/// from the epoch to be dates are written as the seconds.
+fn format_time(time: i64) -> String {
+    match DateTime::from_timestamp(time, 0) {
+        Some(time) => time.format("%Y-%m-%d %H-%M-%S").to_string(),
+        None => time.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn times_are_formatted_as_utc_dates() {
+        assert_eq!(format_time(0), "1970-01-01 00-00-00");
+        assert_eq!(format_time(1_659_789_296), "2022-08-06 12-34-56");
+        assert_eq!(format_time(1_582_934_399), "2020-02-28 23-59-59");
+        assert_eq!(format_time(1_582_934_400), "2020-02-29 00-00-00");
+    }
+
+    #[test]
+    fn file_names_leave_out_reserved_characters() {
+        let station = Station {
+            name: "AC/DC: Live?".to_owned(),
+            codec: "MP3".to_owned(),
+            ..Station::default()
+        };
+        assert_eq!(
+            recording_file_name(&station, 0),
+            "ACDC Live 1970-01-01 00-00-00.mp3"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::radio_browser::Station;
+use std::io;
+use std::path::PathBuf;
+
+/// Create a launcher that starts the application playing the given station.
+/// Return the path of the created launcher.
+pub fn create_station_shortcut(station: &Station) -> io::Result<PathBuf> {
+    // The UUID is used in the file name, so only keep the expected characters.
+    let uuid: String = station
+        .stationuuid
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit() || *c == '-')
+        .collect();
+    if uuid.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the station has no UUID",
+        ));
+    }
+
+    // The launcher runs this same executable.
+    let executable = std::env::current_exe()?;
+
+    write_launcher(&uuid, &station.name, &executable.to_string_lossy())
+}
+
+/// Write a desktop entry to the user applications directory, so the station
+/// shows up in the application launcher.
+#[cfg(target_os = "linux")]
+fn write_launcher(uuid: &str, name: &str, executable: &str) -> io::Result<PathBuf> {
+    // Use $XDG_DATA_HOME/applications, or ~/.local/share/applications.
+    let data_home = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => match std::env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(".local").join("share"),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "the home directory is unknown",
+                ))
+            }
+        },
+    };
+    let directory = data_home.join("applications");
+    std::fs::create_dir_all(&directory)?;
+
+    // Keys may not span lines, so remove line breaks from the name.
+    let name = name.replace(['\n', '\r'], " ");
+    // Quote the executable path, escaping the characters that are reserved
+    // inside quoted arguments.
+    let mut quoted_executable = String::new();
+    for c in executable.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted_executable.push('\\');
+        }
+        quoted_executable.push(c);
+    }
+
+    let contents = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={} - Online Radio\n\
+         Exec=\"{}\" --station {}\n\
+         Icon=audio-x-generic\n\
+         Categories=Audio;Player;\n",
+        name, quoted_executable, uuid
+    );
+
+    let path = directory.join(format!("radio-{}.desktop", uuid));
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Launchers are only supported on Linux desktops.
+#[cfg(not(target_os = "linux"))]
+fn write_launcher(_uuid: &str, _name: &str, _executable: &str) -> io::Result<PathBuf> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "shortcuts are only supported on Linux",
+    ))
+}
@@ -0,0 +1,405 @@
+#[cfg(not(target_arch = "wasm32"))]
+use super::equalizer::Preset;
+use super::profile::{StationProfile, VOLUME_OFFSET_RANGE};
+use super::translation::{t, tf};
+use super::unix_time;
+use super::window::show_window;
+use super::Language;
+use crate::radio_browser::{parse_timestamp, Station};
+use eframe::egui;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// How long the stream of a station may take to answer a check, in seconds,
+/// before it is reported unreachable.
+const CHECK_TIMEOUT: f64 = 10.0;
+
+/// The check of whether the stream of a station can be reached.
+enum Check {
+    /// The stream was asked for its headers at the given time, in seconds,
+    /// and has not answered yet.
+    InProgress(f64),
+    /// The stream answered, or could not be reached, as described.
+    Done(Result<String, String>),
+}
+
+/// The station window shows the details of a station, such as its homepage,
+/// tags, and location. Only one station is shown at a time.
+#[derive(Default)]
+pub struct StationWindow {
+    /// The station whose details are shown, if the window is open.
+    station: Option<Station>,
+
+    /// The check of the stream of the shown station, if the user asked for
+    /// one. Every station has its own check, so a late answer about another
+    /// station is dropped.
+    check: Arc<Mutex<Option<Check>>>,
+}
+
+impl StationWindow {
+    /// Show the details of the station, or close the window if it already
+    /// shows them.
+    pub fn toggle(&mut self, station: &Station) {
+        let is_shown = self
+            .station
+            .as_ref()
+            .is_some_and(|shown| shown.stationuuid == station.stationuuid);
+        self.station = if is_shown {
+            None
+        } else {
+            Some(station.clone())
+        };
+        self.check = Arc::default();
+    }
+}
+
+/// Ask the stream of the station for its headers, without playing it, and
+/// store whether it answered in the check. Redirects are followed, and the
+/// final address is reported if it differs.
+fn check_station(
+    ctx: &egui::Context,
+    lang: Language,
+    station: &Station,
+    check: &Arc<Mutex<Option<Check>>>,
+) {
+    *check.lock().unwrap() = Some(Check::InProgress(ctx.input().time));
+
+    // Only the headers are asked for, since the stream never ends.
+    let url = station.stream_url().to_owned();
+    let mut request = ehttp::Request::get(&url);
+    request.method = "HEAD".to_owned();
+
+    // Insecure streams are blocked silently on secure pages.
+    let insecure = cfg!(target_arch = "wasm32") && !station.is_https();
+
+    let ctx = ctx.clone();
+    let check = check.clone();
+    ehttp::fetch(request, move |response| {
+        let result = match response {
+            Ok(response) => {
+                let content_type = response
+                    .content_type()
+                    .unwrap_or_else(|| t(lang, "unknown content"));
+                let status: [&dyn std::fmt::Display; 2] = [&response.status, &response.status_text];
+                let mut result = match response.status {
+                    // Some servers refuse requests for the headers alone, which
+                    // still shows they are up.
+                    405 | 501 => Ok(tf(
+                        lang,
+                        "Reachable, but the server does not describe the stream ({} {}).",
+                        &status,
+                    )),
+                    _ if response.ok => Ok(tf(
+                        lang,
+                        "Reachable ({} {}, {}).",
+                        &[&response.status, &response.status_text, &content_type],
+                    )),
+                    _ => Err(tf(lang, "Unreachable ({} {}).", &status)),
+                };
+                if response.url != url {
+                    let (Ok(text) | Err(text)) = &mut result;
+                    *text += " ";
+                    *text += &tf(lang, "Redirected to {}.", &[&response.url]);
+                }
+                result
+            }
+            Err(_) if insecure => Err(t(
+                lang,
+                "Unreachable. The browser blocks insecure streams on secure pages.",
+            )
+            .to_owned()),
+            Err(err) => Err(tf(lang, "Unreachable: {}.", &[&err])),
+        };
+
+        // Drop the result if the check timed out.
+        let mut check = check.lock().unwrap();
+        if matches!(*check, Some(Check::InProgress(_))) {
+            *check = Some(Check::Done(result));
+        }
+        ctx.request_repaint();
+    });
+}
+
+impl StationWindow {
+    /// Show the details of the station, if any, in the given language, and
+    /// let the user edit its profile. Return the UUID of the station if its
+    /// profile changed.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        lang: Language,
+        profiles: &mut HashMap<String, StationProfile>,
+    ) -> Option<String> {
+        let station = match &self.station {
+            Some(station) => station,
+            None => return None,
+        };
+        let mut profile = profiles
+            .get(&station.stationuuid)
+            .cloned()
+            .unwrap_or_default();
+        let mut profile_changed = false;
+
+        // The title is the same for every station, so the window keeps its
+        // place when another station is shown.
+        let mut is_open = true;
+        let title = format!("ℹ {}", t(lang, "Station details"));
+        show_window(ctx, title, &mut is_open, |ui| {
+            ui.heading(&station.name);
+
+            egui::Grid::new("station_details")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(t(lang, "Homepage:"));
+                    ui.horizontal(|ui| {
+                        homepage_button(ui, station).on_hover_text(t(lang, "Open homepage"));
+                        if station.homepage.is_empty() {
+                            ui.label(t(lang, "Unknown"));
+                        } else {
+                            ui.hyperlink_to(&station.homepage, &station.homepage);
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(t(lang, "Tags:"));
+                    tag_chips(ui, &station.tags);
+                    ui.end_row();
+
+                    ui.label(t(lang, "Country:"));
+                    ui.label(&station.country);
+                    ui.end_row();
+
+                    ui.label(t(lang, "Region:"));
+                    ui.label(&station.state);
+                    ui.end_row();
+
+                    ui.label(t(lang, "Language:"));
+                    ui.label(&station.language);
+                    ui.end_row();
+
+                    ui.label(t(lang, "Stream:"));
+                    ui.label(format!("{} kbps {}", station.bitrate, station.codec));
+                    ui.end_row();
+
+                    ui.label(t(lang, "Votes:"));
+                    ui.label(station.votes.to_string());
+                    ui.end_row();
+
+                    ui.label(t(lang, "Clicks:"));
+                    // The trend is the change in clicks over the last two days.
+                    ui.label(tf(
+                        lang,
+                        "{} ({} in two days)",
+                        &[&station.clickcount, &format!("{:+}", station.clicktrend)],
+                    ));
+                    ui.end_row();
+
+                    // Show how long ago the station changed and worked, and
+                    // the exact time when hovered.
+                    let now = unix_time();
+                    ui.label(t(lang, "Last changed:"));
+                    ui.label(tf(
+                        lang,
+                        "updated {}",
+                        &[&time_ago(lang, &station.lastchangetime, now)],
+                    ))
+                    .on_hover_text(&station.lastchangetime);
+                    ui.end_row();
+
+                    ui.label(t(lang, "Last online:"));
+                    ui.label(tf(
+                        lang,
+                        "checked {}",
+                        &[&time_ago(lang, &station.lastcheckoktime, now)],
+                    ))
+                    .on_hover_text(&station.lastcheckoktime);
+                    ui.end_row();
+                });
+
+            // Let the user check that the stream is up, without playing it.
+            ui.horizontal(|ui| {
+                let mut check = self.check.lock().unwrap();
+                if let Some(Check::InProgress(started_at)) = *check {
+                    if ctx.input().time - started_at >= CHECK_TIMEOUT {
+                        *check = Some(Check::Done(Err(tf(
+                            lang,
+                            "Unreachable: no answer after {} seconds.",
+                            &[&CHECK_TIMEOUT],
+                        ))));
+                    }
+                }
+                let in_progress = matches!(*check, Some(Check::InProgress(_)));
+                if ui
+                    .add_enabled(!in_progress, egui::Button::new(t(lang, "Check")))
+                    .on_hover_text(t(lang, "Check that the stream is up, without playing it"))
+                    .clicked()
+                {
+                    drop(check);
+                    check_station(ctx, lang, station, &self.check);
+                    return;
+                }
+                match &*check {
+                    None => {}
+                    // Keep repainting so the spinner turns, and the check
+                    // times out.
+                    Some(Check::InProgress(_)) => {
+                        ui.add(egui::Spinner::new());
+                        ctx.request_repaint();
+                    }
+                    Some(Check::Done(Ok(text))) => {
+                        ui.colored_label(egui::Color32::GREEN, text);
+                    }
+                    Some(Check::Done(Err(text))) => {
+                        ui.colored_label(egui::Color32::RED, text);
+                    }
+                }
+            });
+
+            ui.separator();
+            profile_changed = profile_ui(ui, lang, &mut profile);
+        });
+
+        // Neutral profiles are dropped, so only changed stations are stored.
+        let uuid = station.stationuuid.clone();
+        if !is_open {
+            self.station = None;
+        }
+        if !profile_changed {
+            return None;
+        }
+        if profile.is_neutral() {
+            profiles.remove(&uuid);
+        } else {
+            profiles.insert(uuid.clone(), profile);
+        }
+        Some(uuid)
+    }
+}
+
+/// Show the controls of the profile of a station, which is applied whenever
+/// the station plays. Return whether the profile changed.
+fn profile_ui(ui: &mut egui::Ui, lang: Language, profile: &mut StationProfile) -> bool {
+    let mut changed = false;
+    ui.label(t(lang, "Profile"));
+    ui.horizontal(|ui| {
+        ui.label(t(lang, "Volume offset:"));
+        changed |= ui
+            .add(egui::Slider::new(&mut profile.volume_offset, VOLUME_OFFSET_RANGE).suffix("%"))
+            .on_hover_text(t(lang, "Added to the volume while the station plays"))
+            .changed();
+    });
+    #[cfg(not(target_arch = "wasm32"))]
+    ui.horizontal(|ui| {
+        ui.label(t(lang, "Preset: "));
+        let label = |preset: Option<Preset>| match preset {
+            Some(preset) => t(lang, preset.label()),
+            None => t(lang, "Equalizer of the options"),
+        };
+        egui::ComboBox::from_id_source("profile_equalizer")
+            .selected_text(label(profile.equalizer))
+            .show_ui(ui, |ui| {
+                let presets = std::iter::once(None).chain(Preset::ALL.map(Some));
+                for preset in presets {
+                    if ui
+                        .selectable_label(profile.equalizer == preset, label(preset))
+                        .clicked()
+                    {
+                        profile.equalizer = preset;
+                        changed = true;
+                    }
+                }
+            });
+    });
+    if ui
+        .add_enabled(
+            !profile.is_neutral(),
+            egui::Button::new(t(lang, "Reset profile")),
+        )
+        .clicked()
+    {
+        *profile = StationProfile::default();
+        changed = true;
+    }
+    changed
+}
+
+/// Return how long before the given time, in seconds since the Unix epoch, the
+/// timestamp is, such as "2 hours ago", in the given language. Return
+/// "unknown" for empty or malformed timestamps.
+fn time_ago(lang: Language, timestamp: &str, now: i64) -> String {
+    let seconds = match parse_timestamp(timestamp) {
+        Some(time) => now - time,
+        None => return t(lang, "unknown").to_owned(),
+    };
+    // Timestamps slightly ahead of the clock are recent.
+    let (count, one, many) = match seconds {
+        i64::MIN..=59 => return t(lang, "just now").to_owned(),
+        60..=3_599 => (seconds / 60, "{} minute ago", "{} minutes ago"),
+        3_600..=86_399 => (seconds / 3_600, "{} hour ago", "{} hours ago"),
+        86_400..=2_591_999 => (seconds / 86_400, "{} day ago", "{} days ago"),
+        2_592_000..=31_535_999 => (seconds / 2_592_000, "{} month ago", "{} months ago"),
+        _ => (seconds / 31_536_000, "{} year ago", "{} years ago"),
+    };
+    tf(lang, if count == 1 { one } else { many }, &[&count])
+}
+
+/// Show the comma separated tags as chips.
+pub fn tag_chips(ui: &mut egui::Ui, tags: &str) {
+    ui.horizontal_wrapped(|ui| {
+        for tag in tags.split(',').map(str::trim) {
+            if !tag.is_empty() {
+                ui.label(
+                    egui::RichText::new(tag)
+                        .small()
+                        .background_color(ui.visuals().faint_bg_color),
+                );
+            }
+        }
+    });
+}
+
+/// Add a button that opens the homepage of the station in the browser. The
+/// button is disabled if the station has no homepage.
+pub fn homepage_button(ui: &mut egui::Ui, station: &Station) -> egui::Response {
+    let response = ui.add_enabled(!station.homepage.is_empty(), egui::Button::new("🌐"));
+    if response.clicked() {
+        ui.ctx().output().open_url(&station.homepage);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A time at which the station last changed.
+    const CHANGED: &str = "2022-08-06 12:00:00";
+    /// The same time in seconds since the Unix epoch.
+    const CHANGED_AT: i64 = 1_659_787_200;
+
+    #[test]
+    fn time_ago_changes_unit_at_the_boundaries() {
+        let ago = |seconds| time_ago(Language::English, CHANGED, CHANGED_AT + seconds);
+        assert_eq!(ago(-5), "just now");
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(3_599), "59 minutes ago");
+        assert_eq!(ago(3_600), "1 hour ago");
+        assert_eq!(ago(86_399), "23 hours ago");
+        assert_eq!(ago(86_400), "1 day ago");
+        assert_eq!(ago(2_591_999), "29 days ago");
+        assert_eq!(ago(2_592_000), "1 month ago");
+        assert_eq!(ago(31_535_999), "12 months ago");
+        assert_eq!(ago(31_536_000), "1 year ago");
+        assert_eq!(ago(63_072_000), "2 years ago");
+    }
+
+    #[test]
+    fn unknown_timestamps_have_no_age() {
+        assert_eq!(time_ago(Language::English, "", CHANGED_AT), "unknown");
+        assert_eq!(
+            time_ago(Language::English, "yesterday", CHANGED_AT),
+            "unknown"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use super::Language;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Translate the English user interface text into the given language. Text
+/// without a translation is looked up in the English locale, and returned as
+/// is if it has no entry there either.
+pub fn t(language: Language, text: &'static str) -> &'static str {
+    translations(language)
+        .get(text)
+        .or_else(|| translations(Language::English).get(text))
+        .map_or(text, String::as_str)
+}
+
+/// Translate the English text like t(), and replace each "{}" in the
+/// translation with the next argument, in order.
+pub fn tf(language: Language, text: &'static str, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = t(language, text).split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_owned();
+    for part in parts {
+        match args.next() {
+            Some(arg) => filled += &arg.to_string(),
+            None => filled += "{}",
+        }
+        filled += part;
+    }
+    filled
+}
+
+/// Return the translations of the English text into the given language. They
+/// are read from the locale file of the language, which is embedded in the
+/// executable, the first time they are needed. Adding a language only takes a
+/// locale file and a variant of the language enum.
+fn translations(language: Language) -> &'static HashMap<String, String> {
+    static ENGLISH: OnceLock<HashMap<String, String>> = OnceLock::new();
+    static SPANISH: OnceLock<HashMap<String, String>> = OnceLock::new();
+    static RUSSIAN: OnceLock<HashMap<String, String>> = OnceLock::new();
+    static GERMAN: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+    let (translations, locale) = match language {
+        Language::English => (&ENGLISH, include_str!("../../locales/en.json")),
+        Language::Spanish => (&SPANISH, include_str!("../../locales/es.json")),
+        Language::Russian => (&RUSSIAN, include_str!("../../locales/ru.json")),
+        Language::German => (&GERMAN, include_str!("../../locales/de.json")),
+    };
+    translations.get_or_init(|| serde_json::from_str(locale).expect("locale files are valid JSON"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_fill_the_placeholders_in_order() {
+        assert_eq!(
+            tf(
+                Language::English,
+                "Unreachable ({} {}).",
+                &[&404, &"Not Found"]
+            ),
+            "Unreachable (404 Not Found)."
+        );
+        assert_eq!(
+            tf(Language::Spanish, "Unreachable: {}.", &[&"timeout"]),
+            "Inaccesible: timeout."
+        );
+    }
+
+    #[test]
+    fn missing_arguments_leave_the_placeholders() {
+        assert_eq!(
+            tf(Language::English, "Version {} ({})", &[&"0.1.7"]),
+            "Version 0.1.7 ({})"
+        );
+    }
+}
@@ -0,0 +1,891 @@
+use super::equalizer::Equalizer;
+use super::player::{AudioDevice, PlaybackState, Player, DEFAULT_METADATA_INTERVAL};
+use super::volume::{DEFAULT_VOLUME, MAX_BOOSTED_VOLUME};
+use eframe::egui;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The VLC volume level that plays at 100%.
+const VLC_FULL_VOLUME: i32 = 256;
+
+/// How often VLC is asked whether it is playing, while connecting.
+const CONNECTING_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a station may take to connect before it is considered dead.
+const CONNECTING_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How often VLC is asked whether it is still playing, so a dropped stream is
+/// noticed, and for the measured bitrate.
+const PLAYING_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many times a dropped station is reconnected before giving up.
+const MAX_RECONNECTS: u32 = 5;
+
+/// How long the previous station fades out, and the new one fades in, when
+/// cross-fading.
+const FADE_DURATION: Duration = Duration::from_millis(500);
+
+/// How long a station fades in when it starts playing, if asked to.
+const FADE_IN_DURATION: Duration = Duration::from_millis(800);
+
+/// How often the volumes change while cross-fading or fading in.
+const FADE_STEP: Duration = Duration::from_millis(50);
+
+/// How long VLC may take to quit before it is killed.
+const QUIT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long to wait before the first reconnection. The wait doubles after
+/// each failed reconnection.
+const FIRST_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Why stations cannot be played when VLC is not installed.
+const VLC_NOT_FOUND: &str = "VLC not found — install VLC to enable playback.";
+
+/// The commands the media player sends to the playback thread.
+enum PlayerCommand {
+    /// Play the station with the given URL, replacing the playing station.
+    /// If asked to, the playing station fades out as the new one fades in.
+    Play(String, bool),
+    /// Stop playing the station.
+    Stop,
+    /// Set the volume level, from 0 to 100.
+    Volume(i32),
+    /// Play through the audio device with the given id, or the default
+    /// device if the id is empty.
+    AudioDevice(String),
+    /// Start VLC with the given equalizer arguments, or without an equalizer
+    /// if there are none.
+    Equalizer(Vec<String>),
+    /// Set whether the stations fade in when they start playing.
+    FadeIn(bool),
+    /// Set how often VLC is asked for the stream metadata, while playing.
+    MetadataInterval(Duration),
+    /// Quit VLC, and end the playback thread.
+    Quit,
+}
+
+/// What VLC reports, shared by the media player and the threads that talk to
+/// VLC.
+#[derive(Clone)]
+struct Shared {
+    /// What VLC is doing.
+    state: Arc<Mutex<PlaybackState>>,
+
+    /// The title of the playing track, as sent by the station.
+    track_title: Arc<Mutex<Option<String>>>,
+
+    /// The user interface to repaint when the track title changes, if known.
+    repaint: Arc<Mutex<Option<egui::Context>>>,
+
+    /// The audio devices VLC can play through, once VLC listed them.
+    audio_devices: Arc<Mutex<Vec<AudioDevice>>>,
+
+    /// The bitrate of the playing stream in kbps, as measured by VLC.
+    bitrate: Arc<Mutex<Option<u32>>>,
+}
+
+/// Plays stations natively with VLC. A single playback thread owns a single
+/// VLC process, and receives the commands over a channel, so the user
+/// interface never waits for VLC, and playing another station replaces the
+/// playing one instead of starting a second VLC.
+///
+/// VLC is driven through its remote control interface (`vlc --intf rc`) over
+/// standard input and output, rather than through libvlc. Binding libvlc
+/// needs unsafe code, which the crate forbids, and the libvlc headers and
+/// library at build time. The remote control interface only needs the VLC
+/// program at run time, so the app builds without VLC, and a crash of VLC
+/// does not bring down the app.
+pub struct VLCMediaPlayer {
+    /// Sends the commands to the playback thread.
+    commands: Sender<PlayerCommand>,
+
+    /// The playback thread, until it is ended.
+    thread: Option<JoinHandle<()>>,
+
+    /// The URL of the station to play.
+    src: String,
+
+    /// Whether to cross-fade from the playing station to the next one.
+    crossfade: bool,
+
+    /// Why VLC cannot be started, if it cannot, as found when the media
+    /// player was created.
+    unavailable: Option<String>,
+
+    /// What VLC reports, as read by the playback thread.
+    shared: Shared,
+}
+
+/// Implement trait to create the default media player.
+impl Default for VLCMediaPlayer {
+    /// Create a media player at the default volume.
+    fn default() -> Self {
+        VLCMediaPlayer::new(DEFAULT_VOLUME)
+    }
+}
+
+impl VLCMediaPlayer {
+    /// Create a media player, and start its playback thread. VLC is started
+    /// when the first station is played, but it is looked for right away, so
+    /// a missing VLC is known before playing.
+    pub fn new(volume: i32) -> Self {
+        let (commands, receiver) = mpsc::channel();
+        let shared = Shared {
+            state: Arc::new(Mutex::new(PlaybackState::Idle)),
+            track_title: Arc::new(Mutex::new(None)),
+            repaint: Arc::new(Mutex::new(None)),
+            audio_devices: Arc::new(Mutex::new(Vec::new())),
+            bitrate: Arc::new(Mutex::new(None)),
+        };
+        let thread_shared = shared.clone();
+        let thread = thread::spawn(move || run_playback(receiver, thread_shared, volume));
+        VLCMediaPlayer {
+            commands,
+            thread: Some(thread),
+            src: String::new(),
+            crossfade: false,
+            unavailable: check_vlc(),
+            shared,
+        }
+    }
+
+    /// Send a command to the playback thread.
+    fn send(&self, command: PlayerCommand) {
+        // The thread only stops when the media player shuts down.
+        let _ = self.commands.send(command);
+    }
+}
+
+/// Implement trait to quit VLC when the media player is dropped.
+impl Drop for VLCMediaPlayer {
+    /// Quit VLC, so no audio is left playing.
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl Player for VLCMediaPlayer {
+    fn set_src(&mut self, url: &str) {
+        // Each line is a command, so a line break would end the URL early.
+        self.src = url.replace(['\n', '\r'], "");
+    }
+
+    fn play(&mut self) {
+        if !self.src.is_empty() {
+            // Set the state right away, so it never lags behind the user
+            // interface. Only a station that can be heard fades out.
+            let mut state = self.shared.state.lock().unwrap();
+            let fade = self.crossfade && *state == PlaybackState::Playing;
+            *state = PlaybackState::Connecting;
+            drop(state);
+            self.send(PlayerCommand::Play(self.src.clone(), fade));
+        }
+    }
+
+    fn pause(&mut self) {
+        *self.shared.state.lock().unwrap() = PlaybackState::Idle;
+        // Nothing is playing until the station is played again.
+        *self.shared.track_title.lock().unwrap() = None;
+        *self.shared.bitrate.lock().unwrap() = None;
+        self.send(PlayerCommand::Stop);
+    }
+
+    fn set_volume(&mut self, volume: i32) {
+        self.send(PlayerCommand::Volume(volume));
+    }
+
+    fn state(&self) -> PlaybackState {
+        self.shared.state.lock().unwrap().clone()
+    }
+
+    fn track_title(&self) -> Option<String> {
+        self.shared.track_title.lock().unwrap().clone()
+    }
+
+    fn measured_bitrate(&self) -> Option<u32> {
+        *self.shared.bitrate.lock().unwrap()
+    }
+
+    fn set_repaint_context(&mut self, ctx: &egui::Context) {
+        *self.shared.repaint.lock().unwrap() = Some(ctx.clone());
+    }
+
+    /// VLC only equalizes the stations it was started with, so a playing
+    /// station is restarted.
+    fn set_equalizer(&mut self, equalizer: &Equalizer) {
+        self.send(PlayerCommand::Equalizer(equalizer.vlc_args()));
+    }
+
+    fn audio_devices(&self) -> Vec<AudioDevice> {
+        self.shared.audio_devices.lock().unwrap().clone()
+    }
+
+    fn shutdown(&mut self) {
+        *self.shared.state.lock().unwrap() = PlaybackState::Idle;
+        // Wait until VLC quits, so it releases the audio device before the
+        // application exits. The thread only ends once.
+        if let Some(thread) = self.thread.take() {
+            self.send(PlayerCommand::Quit);
+            let _ = thread.join();
+        }
+    }
+
+    fn unavailable_reason(&self) -> Option<String> {
+        self.unavailable.clone()
+    }
+
+    fn set_crossfade(&mut self, enabled: bool) {
+        self.crossfade = enabled;
+    }
+
+    fn set_fade_in(&mut self, enabled: bool) {
+        self.send(PlayerCommand::FadeIn(enabled));
+    }
+
+    fn set_metadata_interval(&mut self, seconds: u32) {
+        self.send(PlayerCommand::MetadataInterval(Duration::from_secs(
+            seconds.into(),
+        )));
+    }
+
+    fn set_audio_device(&mut self, id: &str) {
+        // Each line is a command, so a line break would end the id early.
+        self.send(PlayerCommand::AudioDevice(id.replace(['\n', '\r'], "")));
+    }
+}
+
+/// Pass the commands to VLC until the media player shuts down or is dropped,
+/// and then quit VLC.
+fn run_playback(commands: Receiver<PlayerCommand>, shared: Shared, mut volume: i32) {
+    // The running VLC process and its standard input, if VLC was started.
+    let mut process: Option<(Child, ChildStdin)> = None;
+    // The URL of the playing station, to reconnect it if it drops.
+    let mut playing_url = String::new();
+    // When the playing station was added.
+    let mut connecting_since = Instant::now();
+    // When VLC was last asked for the stream metadata, and how often it is
+    // asked. VLC reads the metadata sent within the stream (icy-metaint) as
+    // often as the station sends it, so this is how soon a new title shows.
+    let mut metadata_requested_at = Instant::now();
+    let mut metadata_interval = Duration::from_secs(DEFAULT_METADATA_INTERVAL.into());
+    // When VLC was last asked whether it is still playing.
+    let mut playing_checked_at = Instant::now();
+    // The number of reconnections since the station last played.
+    let mut reconnects = 0;
+    // When to reconnect the dropped station, once the wait is decided.
+    let mut reconnect_at: Option<Instant> = None;
+    // The id of the audio device to play through, or empty for the default.
+    let mut audio_device = String::new();
+    // Whether VLC was told the audio device since the station started playing.
+    let mut audio_device_applied = false;
+    // The arguments VLC is started with to equalize the stations, if any.
+    let mut equalizer_args: Vec<String> = Vec::new();
+    // The VLC process of the previous station while it fades out, and when
+    // the fade started, once the new station can be heard.
+    let mut fading: Option<(Child, ChildStdin, Option<Instant>)> = None;
+    // Whether the stations fade in when they start playing.
+    let mut fade_in = false;
+    // Whether the playing station is fading in, and when the fade started,
+    // once the station can be heard.
+    let mut fading_in: Option<Option<Instant>> = None;
+
+    loop {
+        // While connecting, keep asking VLC whether it is playing. While
+        // playing, ask for the stream metadata from time to time. While
+        // fading, change the volumes often, so the fade is smooth.
+        let poll_interval = if fading.is_some() || fading_in.is_some() {
+            FADE_STEP
+        } else {
+            CONNECTING_POLL_INTERVAL
+        };
+        let command = match commands.recv_timeout(poll_interval) {
+            Ok(command) => Some(command),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        // Keep the previous station playing until the new one can be heard,
+        // then lower its volume as the volume of the new one rises. If the
+        // new station is stopped or cannot be played, quit the previous one.
+        if let Some((_, old_stdin, fade_started)) = &mut fading {
+            let state = shared.state.lock().unwrap().clone();
+            let progress = match state {
+                PlaybackState::Connecting | PlaybackState::Reconnecting => None,
+                PlaybackState::Playing => {
+                    let started = *fade_started.get_or_insert_with(Instant::now);
+                    Some((started.elapsed().as_secs_f32() / FADE_DURATION.as_secs_f32()).min(1.0))
+                }
+                PlaybackState::Idle | PlaybackState::Error(_) => Some(1.0),
+            };
+            if let Some(progress) = progress {
+                let old_volume = ((1.0 - progress) * volume as f32) as i32;
+                let _ = writeln!(old_stdin, "volume {}", vlc_volume(old_volume));
+                if let Some((_, stdin)) = &mut process {
+                    let new_volume = (progress * volume as f32) as i32;
+                    let _ = writeln!(stdin, "volume {}", vlc_volume(new_volume));
+                }
+                if progress >= 1.0 {
+                    if let Some((child, stdin, _)) = fading.take() {
+                        quit_vlc(child, stdin);
+                    }
+                }
+            }
+        }
+
+        // Raise the volume of the new station from silent to the chosen
+        // volume once it can be heard. If the station is stopped or cannot
+        // be played, restore the chosen volume right away.
+        if let Some(fade_started) = &mut fading_in {
+            let state = shared.state.lock().unwrap().clone();
+            let progress = match state {
+                PlaybackState::Connecting | PlaybackState::Reconnecting => None,
+                PlaybackState::Playing => {
+                    let started = *fade_started.get_or_insert_with(Instant::now);
+                    Some(
+                        (started.elapsed().as_secs_f32() / FADE_IN_DURATION.as_secs_f32()).min(1.0),
+                    )
+                }
+                PlaybackState::Idle | PlaybackState::Error(_) => Some(1.0),
+            };
+            if let Some(progress) = progress {
+                if let Some((_, stdin)) = &mut process {
+                    let new_volume = (progress * volume as f32) as i32;
+                    let _ = writeln!(stdin, "volume {}", vlc_volume(new_volume));
+                }
+                if progress >= 1.0 {
+                    fading_in = None;
+                }
+            }
+        }
+
+        let line = match command {
+            Some(PlayerCommand::Play(url, fade)) => {
+                // Start the new station silent, to fade it in, unless it
+                // already fades in as the playing station fades out.
+                if fade_in && !fade {
+                    fading_in = Some(None);
+                    if let Some((_, stdin)) = &mut process {
+                        let _ = writeln!(stdin, "volume 0");
+                    }
+                }
+                // Keep the playing VLC to fade it out, and play the new
+                // station with another VLC.
+                if fade {
+                    if let Some((child, stdin, _)) = fading.take() {
+                        quit_vlc(child, stdin);
+                    }
+                    fading = process.take().map(|(child, stdin)| (child, stdin, None));
+                }
+                log::debug!("Connecting to {}", url);
+                *shared.state.lock().unwrap() = PlaybackState::Connecting;
+                connecting_since = Instant::now();
+                // The station chosen by the user is not a reconnection.
+                reconnects = 0;
+                reconnect_at = None;
+                audio_device_applied = false;
+                // The track of the previous station is no longer playing.
+                *shared.track_title.lock().unwrap() = None;
+                *shared.bitrate.lock().unwrap() = None;
+                // Adding the URL replaces the playing station. Live stations
+                // are always played from the start, so they resume live after
+                // stopping.
+                playing_url = url;
+                format!("add {}", playing_url)
+            }
+            Some(PlayerCommand::Quit) => break,
+            Some(PlayerCommand::Stop) => {
+                // Stopping cancels the pending reconnection, if any.
+                reconnect_at = None;
+                // Stopping also cancels the fade in, so the next station
+                // does not start at the volume the fade reached.
+                if fading_in.take().is_some() {
+                    format!("stop\nvolume {}", vlc_volume(volume))
+                } else {
+                    "stop".to_owned()
+                }
+            }
+            Some(PlayerCommand::Volume(level)) => {
+                // VLC starts with the stored volume. A volume chosen by the
+                // user while fading in ends the fade.
+                volume = level;
+                fading_in = None;
+                format!("volume {}", vlc_volume(level))
+            }
+            Some(PlayerCommand::FadeIn(enabled)) => {
+                fade_in = enabled;
+                continue;
+            }
+            Some(PlayerCommand::MetadataInterval(interval)) => {
+                metadata_interval = interval;
+                continue;
+            }
+            Some(PlayerCommand::AudioDevice(id)) => {
+                audio_device = id;
+                if !audio_device.is_empty() {
+                    format!("adev {}", audio_device)
+                } else {
+                    // VLC lists the devices when given an empty id, instead
+                    // of using the default device, so restart VLC, which
+                    // uses the default device.
+                    restart_vlc(&mut process, &shared, &mut reconnect_at);
+                    continue;
+                }
+            }
+            Some(PlayerCommand::Equalizer(args)) => {
+                // The equalizer can only be set when VLC starts.
+                if args != equalizer_args {
+                    equalizer_args = args;
+                    restart_vlc(&mut process, &shared, &mut reconnect_at);
+                }
+                continue;
+            }
+            None => {
+                let mut state = shared.state.lock().unwrap();
+                match *state {
+                    PlaybackState::Connecting => {
+                        // VLC keeps trying dead stations, so give up after a
+                        // while. A failed reconnection waits and tries again.
+                        if connecting_since.elapsed() > CONNECTING_TIMEOUT {
+                            log::warn!("No answer from {} after connecting", playing_url);
+                            *state = if reconnects == 0 {
+                                PlaybackState::Error("Could not play station.".to_owned())
+                            } else {
+                                PlaybackState::Reconnecting
+                            };
+                            continue;
+                        }
+                        // VLC answers on its standard output, which is read
+                        // by the thread started with VLC.
+                        "is_playing".to_owned()
+                    }
+                    PlaybackState::Reconnecting => {
+                        // Wait longer after each failed reconnection, and give
+                        // up after a few.
+                        if reconnects >= MAX_RECONNECTS {
+                            log::warn!("Gave up reconnecting to {}", playing_url);
+                            *state =
+                                PlaybackState::Error("Could not reconnect to station.".to_owned());
+                            continue;
+                        }
+                        let at = *reconnect_at.get_or_insert_with(|| {
+                            Instant::now() + FIRST_RECONNECT_DELAY * 2u32.pow(reconnects)
+                        });
+                        if Instant::now() < at {
+                            continue;
+                        }
+                        reconnects += 1;
+                        log::info!("Reconnecting to {} (attempt {})", playing_url, reconnects);
+                        reconnect_at = None;
+                        *state = PlaybackState::Connecting;
+                        connecting_since = Instant::now();
+                        audio_device_applied = false;
+                        format!("add {}", playing_url)
+                    }
+                    PlaybackState::Playing => {
+                        // The station plays, so the next drop may be
+                        // reconnected as many times again.
+                        reconnects = 0;
+                        if !audio_device_applied {
+                            // VLC only knows the audio devices once the
+                            // audio output is created, which is when the
+                            // station plays. Play through the chosen device,
+                            // and list the devices.
+                            audio_device_applied = true;
+                            if audio_device.is_empty() {
+                                "adev".to_owned()
+                            } else {
+                                format!("adev {}\nadev", audio_device)
+                            }
+                        } else if metadata_requested_at.elapsed() >= metadata_interval {
+                            metadata_requested_at = Instant::now();
+                            "info".to_owned()
+                        } else if playing_checked_at.elapsed() >= PLAYING_POLL_INTERVAL {
+                            playing_checked_at = Instant::now();
+                            "is_playing\nstats".to_owned()
+                        } else {
+                            continue;
+                        }
+                    }
+                    PlaybackState::Idle | PlaybackState::Error(_) => continue,
+                }
+            }
+        };
+
+        // Start VLC to add a station, on the first play, or again if it
+        // exited. If VLC cannot be started, starting it is tried again on the
+        // next play.
+        if process.is_none() && line.starts_with("add ") {
+            // While fading, the new station starts silent.
+            let start_volume = if fading.is_some() || fading_in.is_some() {
+                0
+            } else {
+                volume
+            };
+            match start_vlc(start_volume, &equalizer_args, &shared) {
+                Ok(started) => process = Some(started),
+                Err(err) => {
+                    log::error!("Could not start VLC: {}", err);
+                    *shared.state.lock().unwrap() = PlaybackState::Error(start_error(&err));
+                    continue;
+                }
+            }
+        }
+
+        // There is nothing to stop or change if VLC is not running.
+        if let Some((_, stdin)) = &mut process {
+            log::trace!("VLC command: {}", line.replace('\n', "; "));
+            // If VLC exited, start it again on the next play.
+            if writeln!(stdin, "{}", line).is_err() {
+                log::error!("VLC stopped unexpectedly");
+                process = None;
+                *shared.state.lock().unwrap() =
+                    PlaybackState::Error("VLC stopped unexpectedly.".to_owned());
+            }
+        }
+    }
+
+    if let Some((child, stdin)) = process {
+        quit_vlc(child, stdin);
+    }
+    if let Some((child, stdin, _)) = fading {
+        quit_vlc(child, stdin);
+    }
+}
+
+/// Quit VLC, so it is started again on the next play, such as to apply new
+/// arguments. A playing station is reconnected right away.
+fn restart_vlc(
+    process: &mut Option<(Child, ChildStdin)>,
+    shared: &Shared,
+    reconnect_at: &mut Option<Instant>,
+) {
+    if let Some((child, stdin)) = process.take() {
+        quit_vlc(child, stdin);
+    }
+    let mut state = shared.state.lock().unwrap();
+    if matches!(*state, PlaybackState::Connecting | PlaybackState::Playing) {
+        *state = PlaybackState::Reconnecting;
+        *reconnect_at = Some(Instant::now());
+    }
+}
+
+/// Ask VLC to quit, and wait until it does, so it releases the audio device.
+/// VLC is killed if it takes too long.
+pub(super) fn quit_vlc(mut child: Child, mut stdin: ChildStdin) {
+    let _ = writeln!(stdin, "quit");
+    drop(stdin);
+
+    let asked_at = Instant::now();
+    while asked_at.elapsed() < QUIT_TIMEOUT {
+        match child.try_wait() {
+            Ok(None) => thread::sleep(CONNECTING_POLL_INTERVAL / 5),
+            // VLC quit, or cannot be waited for.
+            _ => return,
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Convert a volume level from 0 to 100, or to MAX_BOOSTED_VOLUME when
+/// boosting, to the VLC volume level.
+fn vlc_volume(volume: i32) -> i32 {
+    volume.clamp(0, MAX_BOOSTED_VOLUME) * VLC_FULL_VOLUME / 100
+}
+
+/// Return why VLC cannot be started, or None if it can. VLC is asked for its
+/// version, which fails right away if it is not installed. VLC is only asked
+/// once, and the answer is kept for every media player created afterwards.
+fn check_vlc() -> Option<String> {
+    static CHECKED: OnceLock<Option<String>> = OnceLock::new();
+    CHECKED.get_or_init(ask_vlc_version).clone()
+}
+
+/// Ask VLC for its version, and return why it cannot be started, or None if
+/// it can.
+fn ask_vlc_version() -> Option<String> {
+    let started = Command::new("vlc")
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match started {
+        Ok(mut child) => {
+            // Wait for VLC to quit without blocking, so it is not left as a
+            // zombie process.
+            thread::spawn(move || child.wait());
+            None
+        }
+        Err(err) => Some(start_error(&err)),
+    }
+}
+
+/// Describe why VLC could not be started, telling the user how to fix it when
+/// VLC is not installed.
+fn start_error(err: &io::Error) -> String {
+    if err.kind() == io::ErrorKind::NotFound {
+        VLC_NOT_FOUND.to_owned()
+    } else {
+        format!("Could not start VLC: {}.", err)
+    }
+}
+
+/// Start VLC without a window, reading commands from the standard input, and
+/// set its volume. The extra arguments are passed to VLC, such as to equalize
+/// the stations. VLC quits when its standard input is closed.
+fn start_vlc(
+    volume: i32,
+    extra_args: &[String],
+    shared: &Shared,
+) -> io::Result<(Child, ChildStdin)> {
+    log::debug!("Starting VLC at volume {} with {:?}", volume, extra_args);
+    let mut command = Command::new("vlc");
+    command.args(["--intf", "rc", "--no-video", "--quiet"]);
+    command.args(extra_args);
+    // The remote control interface only reads commands from a terminal,
+    // unless told otherwise.
+    #[cfg(not(target_os = "windows"))]
+    command.arg("--rc-fake-tty");
+    // Do not open a console window for the remote control interface.
+    #[cfg(target_os = "windows")]
+    command.arg("--rc-quiet");
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // The standard input and output are always piped, as requested above.
+    let mut stdin = child.stdin.take().expect("VLC standard input is piped");
+    let stdout = child.stdout.take().expect("VLC standard output is piped");
+
+    // Read the answers of VLC until it quits.
+    let thread_shared = shared.clone();
+    thread::spawn(move || read_answers(stdout, thread_shared));
+
+    writeln!(stdin, "volume {}", vlc_volume(volume))?;
+    Ok((child, stdin))
+}
+
+/// Mark the station as playing once VLC answers that it is, or as
+/// reconnecting once VLC answers that a playing station stopped, and store the
+/// track title the station sends. VLC answers the is_playing command with a
+/// line holding 1 or 0, and lists the track title as "| now_playing: <title>"
+/// when answering the info command, and the stats command lists the bitrate
+/// as "| input bitrate : <rate> kb/s". The adev command lists the audio devices
+/// as "| <id> - <name>" lines, between "+----[ Audio Device ]" and
+/// "+----[ end of Audio Device ]". Answers may start with a prompt.
+fn read_answers(stdout: ChildStdout, shared: Shared) {
+    // The audio devices listed so far, while VLC lists them.
+    let mut audio_devices: Option<Vec<AudioDevice>> = None;
+
+    // Titles are read as bytes, since stations may send them in another
+    // encoding than UTF-8.
+    for line in BufReader::new(stdout).split(b'\n') {
+        let line = match line {
+            Ok(line) => decode_line(&line),
+            Err(_) => break,
+        };
+        let line = line.trim_start_matches(['>', ' ']).trim();
+
+        if line == "+----[ Audio Device ]" {
+            audio_devices = Some(Vec::new());
+        } else if line == "+----[ end of Audio Device ]" {
+            if let Some(devices) = audio_devices.take() {
+                *shared.audio_devices.lock().unwrap() = devices;
+            }
+        } else if let Some(devices) = &mut audio_devices {
+            // The chosen device is marked with an asterisk. The default
+            // device has an empty id.
+            if let Some((id, name)) = line
+                .strip_prefix('|')
+                .and_then(|device| device.split_once(" - "))
+            {
+                devices.push(AudioDevice {
+                    id: id.trim().to_owned(),
+                    name: name.trim_end_matches('*').trim().to_owned(),
+                });
+            }
+        } else if let Some(rate) = line.strip_prefix("| input bitrate") {
+            let rate = rate
+                .trim_start_matches([' ', ':'])
+                .trim_end_matches("kb/s")
+                .trim()
+                .parse::<f32>()
+                .ok()
+                .map(|rate| rate.round() as u32);
+
+            // Only repaint when the bitrate changes.
+            let mut bitrate = shared.bitrate.lock().unwrap();
+            if *bitrate != rate {
+                *bitrate = rate;
+                if let Some(ctx) = &*shared.repaint.lock().unwrap() {
+                    ctx.request_repaint();
+                }
+            }
+        } else if let Some(title) = line.strip_prefix("| now_playing:") {
+            // Keep the previous title rather than showing a garbled one.
+            let title = match parse_track_title(title) {
+                TrackTitle::Missing => None,
+                TrackTitle::Known(title) => Some(title),
+                TrackTitle::Garbled => continue,
+            };
+
+            // Only repaint when the track changes.
+            let mut track_title = shared.track_title.lock().unwrap();
+            if *track_title != title {
+                *track_title = title;
+                if let Some(ctx) = &*shared.repaint.lock().unwrap() {
+                    ctx.request_repaint();
+                }
+            }
+        } else if line == "1" {
+            let mut state = shared.state.lock().unwrap();
+            if *state == PlaybackState::Connecting {
+                log::debug!("The station plays");
+                *state = PlaybackState::Playing;
+            }
+        } else if line == "0" {
+            // The station dropped, unless the user stopped it.
+            let mut state = shared.state.lock().unwrap();
+            if *state == PlaybackState::Playing {
+                log::info!("The station dropped");
+                *state = PlaybackState::Reconnecting;
+                if let Some(ctx) = &*shared.repaint.lock().unwrap() {
+                    ctx.request_repaint();
+                }
+            }
+        }
+    }
+}
+
+/// The longest track title, in bytes, which is the longest metadata a
+/// station can send at once. Longer titles are garbled.
+const MAX_TITLE_LEN: usize = 255 * 16;
+
+/// The title of the playing track, as reported by VLC.
+#[derive(Debug, PartialEq)]
+enum TrackTitle {
+    /// The station sends no title.
+    Missing,
+    /// The station sends the given title.
+    Known(String),
+    /// The station sent a title that cannot be read, which is ignored.
+    Garbled,
+}
+
+/// Decode a line answered by VLC. Lines that are not UTF-8 are read as
+/// Latin-1, which is what most stations that do not send UTF-8 titles use.
+fn decode_line(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(line) => line.to_owned(),
+        Err(_) => bytes.iter().map(|&byte| char::from(byte)).collect(),
+    }
+}
+
+/// Read the track title reported by VLC. Some stations send the raw
+/// metadata, such as "StreamTitle='Artist - Song';", which is unwrapped.
+/// Control characters are dropped, and a title with no letters or digits,
+/// such as " - ", is no title.
+fn parse_track_title(title: &str) -> TrackTitle {
+    if title.len() > MAX_TITLE_LEN {
+        return TrackTitle::Garbled;
+    }
+    let mut title = title.trim();
+    if let Some(raw) = title.strip_prefix("StreamTitle='") {
+        // The title ends at the quote that ends the field, and other fields,
+        // such as StreamUrl, may follow.
+        title = match raw.split_once("';") {
+            Some((inner, _)) => inner,
+            None => match raw.strip_suffix('\'') {
+                Some(inner) => inner,
+                None => return TrackTitle::Garbled,
+            },
+        };
+    }
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    let title = title.trim();
+    if title.chars().any(char::is_alphanumeric) {
+        TrackTitle::Known(title.to_owned())
+    } else {
+        TrackTitle::Missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read the title from the bytes of the line answered by VLC.
+    fn title_of(bytes: &[u8]) -> TrackTitle {
+        let line = decode_line(bytes);
+        parse_track_title(line.strip_prefix("| now_playing:").unwrap())
+    }
+
+    fn known(title: &str) -> TrackTitle {
+        TrackTitle::Known(title.to_owned())
+    }
+
+    #[test]
+    fn titles_are_read_in_utf8_or_latin1() {
+        assert_eq!(
+            title_of(b"| now_playing: Artist - Song"),
+            known("Artist - Song")
+        );
+        assert_eq!(
+            title_of("| now_playing: Café - Ñandú".as_bytes()),
+            known("Café - Ñandú")
+        );
+        assert_eq!(
+            title_of(b"| now_playing: Caf\xe9 - \xd1and\xfa"),
+            known("Café - Ñandú")
+        );
+    }
+
+    #[test]
+    fn empty_titles_are_missing() {
+        assert_eq!(title_of(b"| now_playing:"), TrackTitle::Missing);
+        assert_eq!(title_of(b"| now_playing:  - "), TrackTitle::Missing);
+        assert_eq!(
+            title_of(b"| now_playing: StreamTitle='';"),
+            TrackTitle::Missing
+        );
+    }
+
+    #[test]
+    fn raw_metadata_is_unwrapped() {
+        assert_eq!(
+            title_of(b"| now_playing: StreamTitle='Guns N' Roses - Patience';"),
+            known("Guns N' Roses - Patience")
+        );
+        assert_eq!(
+            title_of(b"| now_playing: StreamTitle='Artist - Song';StreamUrl='';"),
+            known("Artist - Song")
+        );
+        assert_eq!(
+            title_of(b"| now_playing: StreamTitle='Artist - So"),
+            TrackTitle::Garbled
+        );
+    }
+
+    #[test]
+    fn control_characters_are_dropped() {
+        assert_eq!(
+            title_of(b"| now_playing: Artist\x00\x01 - Song\x1b"),
+            known("Artist - Song")
+        );
+    }
+
+    #[test]
+    fn over_long_titles_are_garbled() {
+        let mut line = b"| now_playing: ".to_vec();
+        line.resize(line.len() + MAX_TITLE_LEN + 1, b'a');
+        assert_eq!(title_of(&line), TrackTitle::Garbled);
+    }
+}
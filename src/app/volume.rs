@@ -0,0 +1,295 @@
+use std::fmt;
+
+/// The initial media player volume.
+pub const DEFAULT_VOLUME: i32 = 50;
+
+/// The highest volume level when the volume boost is allowed, which amplifies
+/// quiet stations above their own level.
+pub const MAX_BOOSTED_VOLUME: i32 = 200;
+
+/// Return the highest volume level, which is 100 unless the volume boost is
+/// allowed.
+pub fn max_volume(allow_boost: bool) -> i32 {
+    if allow_boost {
+        MAX_BOOSTED_VOLUME
+    } else {
+        100
+    }
+}
+
+/// A volume level outside the range the media player accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VolumeError {
+    /// The rejected volume level.
+    pub level: i32,
+    /// The highest volume level accepted.
+    pub max: i32,
+}
+
+impl fmt::Display for VolumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "volume {} is not from 0 to {}", self.level, self.max)
+    }
+}
+
+impl std::error::Error for VolumeError {}
+
+/// Return the volume level if it is from 0 to the given highest level, or an
+/// error otherwise. The user interface clamps the levels instead, so this is
+/// for callers that should not have their level changed silently.
+pub fn validate_volume(level: i32, max: i32) -> Result<i32, VolumeError> {
+    if (0..=max).contains(&level) {
+        Ok(level)
+    } else {
+        Err(VolumeError { level, max })
+    }
+}
+
+/// The volume level and the mute state. Both the mute button and the volume
+/// slider change the volume through this type, so they always agree on
+/// whether the volume is mute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// It derives Deserialize/Serialize so it can persist app state on shutdown.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub struct Volume {
+    /// The volume level shown at all times, from 0 to 100, or to
+    /// MAX_BOOSTED_VOLUME when boosting.
+    pub on_slider: i32,
+
+    /// The volume level stored before muting the volume.
+    pub before_mute: i32,
+
+    /// Whether the volume is mute.
+    pub is_muted: bool,
+
+    /// The volume level when the user started dragging the slider, while
+    /// dragging it.
+    /// Opt-out of serialization for the drag start.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    drag_start: Option<i32>,
+}
+
+/// Implement trait to create the default volume.
+impl Default for Volume {
+    /// Create the default volume, which is not mute.
+    fn default() -> Self {
+        Volume {
+            on_slider: DEFAULT_VOLUME,
+            before_mute: DEFAULT_VOLUME,
+            is_muted: false,
+            drag_start: None,
+        }
+    }
+}
+
+impl Volume {
+    /// Create a volume at the given level, which is mute if the level is 0.
+    /// Levels above 100 are kept, up to MAX_BOOSTED_VOLUME, and lowered with
+    /// limit() if the volume boost is not allowed.
+    pub fn new(level: i32) -> Self {
+        let level = level.clamp(0, MAX_BOOSTED_VOLUME);
+        Volume {
+            on_slider: level,
+            before_mute: if level == 0 { DEFAULT_VOLUME } else { level },
+            is_muted: level == 0,
+            drag_start: None,
+        }
+    }
+
+    /// Mute the volume if it is not mute. Else retrieve the volume level
+    /// stored before muting.
+    pub fn toggle_mute(&mut self) {
+        if self.is_muted {
+            self.on_slider = self.before_mute;
+            self.is_muted = false;
+        } else {
+            // Store the current volume level, unless it is already 0.
+            if self.on_slider != 0 {
+                self.before_mute = self.on_slider;
+            }
+            self.on_slider = 0;
+            self.is_muted = true;
+        }
+    }
+
+    /// Remember the volume level the slider was dragged from, so that
+    /// dragging the slider to 0 mutes the volume at that level, rather than
+    /// at the level of the last step of the drag.
+    pub fn drag_started(&mut self, level: i32) {
+        self.drag_start = Some(level);
+    }
+
+    /// Forget the volume level the slider was dragged from.
+    pub fn drag_stopped(&mut self) {
+        self.drag_start = None;
+    }
+
+    /// Update the mute state after the slider moved from the previous level.
+    /// Reaching 0 mutes the volume and stores the previous level, or the
+    /// level the slider was dragged from, so that unmuting restores it.
+    /// Moving above 0 unmutes the volume.
+    pub fn slider_moved(&mut self, previous: i32) {
+        if self.on_slider == 0 {
+            let previous = self.drag_start.unwrap_or(previous);
+            if previous != 0 {
+                self.before_mute = previous;
+            }
+            self.is_muted = true;
+        } else {
+            self.is_muted = false;
+        }
+    }
+
+    /// Raise or lower the volume level by the given step, keeping it from 0
+    /// to the given highest level, as if the slider moved.
+    pub fn change_by(&mut self, step: i32, max: i32) {
+        let previous = self.on_slider;
+        self.on_slider = (self.on_slider + step).clamp(0, max);
+        self.slider_moved(previous);
+    }
+
+    /// Lower the volume levels above the given highest level to it, such as
+    /// when the volume boost is no longer allowed.
+    pub fn limit(&mut self, max: i32) {
+        self.on_slider = self.on_slider.min(max);
+        self.before_mute = self.before_mute.min(max);
+    }
+
+    /// Chose correct volume icon based on volume level.
+    pub fn icon(&self) -> &'static str {
+        if self.is_muted {
+            return "🔇";
+        }
+        volume_icon(self.on_slider)
+    }
+}
+
+/// Chose correct volume icon based on volume level.
+pub fn volume_icon(volume: i32) -> &'static str {
+    match volume {
+        // If volume is 0:
+        0 => "🔇",
+        // If volume is between 1 and 30:
+        1..=30 => "🔈",
+        // If volume is between 31 and 70:
+        31..=70 => "🔉",
+        // For any other value:
+        _ => "🔊",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icon_matches_the_volume_level() {
+        assert_eq!(volume_icon(0), "🔇");
+        assert_eq!(volume_icon(1), "🔈");
+        assert_eq!(volume_icon(30), "🔈");
+        assert_eq!(volume_icon(31), "🔉");
+        assert_eq!(volume_icon(70), "🔉");
+        assert_eq!(volume_icon(71), "🔊");
+        assert_eq!(volume_icon(100), "🔊");
+    }
+
+    #[test]
+    fn levels_out_of_range_are_rejected() {
+        assert_eq!(
+            validate_volume(-1, 100),
+            Err(VolumeError {
+                level: -1,
+                max: 100
+            })
+        );
+        assert_eq!(validate_volume(0, 100), Ok(0));
+        assert_eq!(validate_volume(100, 100), Ok(100));
+        assert_eq!(
+            validate_volume(101, 100),
+            Err(VolumeError {
+                level: 101,
+                max: 100
+            })
+        );
+    }
+
+    #[test]
+    fn levels_out_of_range_are_clamped_for_the_slider() {
+        assert_eq!(Volume::new(-1).on_slider, 0);
+        assert!(Volume::new(-1).is_muted);
+        assert_eq!(Volume::new(0).on_slider, 0);
+        assert_eq!(Volume::new(100).on_slider, 100);
+        let mut volume = Volume::new(101);
+        volume.limit(100);
+        assert_eq!(volume.on_slider, 100);
+    }
+
+    #[test]
+    fn muting_and_unmuting_restores_the_level() {
+        let mut volume = Volume::new(40);
+        volume.toggle_mute();
+        assert!(volume.is_muted);
+        assert_eq!(volume.on_slider, 0);
+        assert_eq!(volume.icon(), "🔇");
+
+        volume.toggle_mute();
+        assert!(!volume.is_muted);
+        assert_eq!(volume.on_slider, 40);
+        assert_eq!(volume.icon(), "🔉");
+    }
+
+    #[test]
+    fn sliding_to_zero_mutes() {
+        let mut volume = Volume::new(20);
+        volume.on_slider = 0;
+        volume.slider_moved(20);
+        assert!(volume.is_muted);
+        assert_eq!(volume.before_mute, 20);
+    }
+
+    #[test]
+    fn mute_button_mutes() {
+        let mut volume = Volume::new(75);
+        volume.toggle_mute();
+        assert!(volume.is_muted);
+        assert_eq!(volume.on_slider, 0);
+        assert_eq!(volume.before_mute, 75);
+    }
+
+    #[test]
+    fn unmuting_restores_the_level_before_sliding_to_zero() {
+        let mut volume = Volume::new(35);
+        volume.on_slider = 0;
+        volume.slider_moved(35);
+        volume.toggle_mute();
+        assert!(!volume.is_muted);
+        assert_eq!(volume.on_slider, 35);
+    }
+
+    #[test]
+    fn sliding_above_zero_unmutes() {
+        let mut volume = Volume::new(50);
+        volume.toggle_mute();
+        volume.on_slider = 10;
+        volume.slider_moved(0);
+        assert!(!volume.is_muted);
+        assert_eq!(volume.on_slider, 10);
+    }
+
+    #[test]
+    fn dragging_to_zero_restores_the_level_the_drag_started_at() {
+        let mut volume = Volume::new(60);
+        volume.drag_started(60);
+        // Every step of the drag moves the slider, down to 0.
+        for level in [40, 20, 2, 1, 0] {
+            let previous = volume.on_slider;
+            volume.on_slider = level;
+            volume.slider_moved(previous);
+        }
+        volume.drag_stopped();
+        assert!(volume.is_muted);
+
+        volume.toggle_mute();
+        assert_eq!(volume.on_slider, 60);
+    }
+}
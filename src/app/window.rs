@@ -0,0 +1,14 @@
+use eframe::egui;
+
+/// Show a window with the given title and a close button. Nothing is shown
+/// while the window is closed, and the close button closes it. The closure
+/// adds the contents of the window, so every window of the application has
+/// the same frame.
+pub fn show_window(
+    ctx: &egui::Context,
+    title: impl Into<egui::WidgetText>,
+    open: &mut bool,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    egui::Window::new(title).open(open).show(ctx, add_contents);
+}
@@ -3,7 +3,11 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
-pub use app::App;
+#[cfg(not(target_arch = "wasm32"))]
+pub use app::{install_panic_hook, play_headless};
+pub use app::{App, Startup};
+
+pub mod radio_browser;
 
 // When compiling for web:
 #[cfg(target_arch = "wasm32")]
@@ -16,6 +20,35 @@ use eframe::wasm_bindgen::{self, prelude::*};
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn start(canvas_id: &str) -> Result<(), eframe::wasm_bindgen::JsValue> {
+    // Print panics to the browser console, which otherwise only reports that
+    // unreachable code was executed. The panic hook of eframe calls this one.
+    std::panic::set_hook(Box::new(|panic_info| {
+        web_sys::console::error_1(&JsValue::from_str(&panic_info.to_string()));
+    }));
+
+    // Read the parameters of the page address, such as "?station=<uuid>".
+    let search = web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .unwrap_or_default();
+    let parameter = |name: &str| {
+        url::form_urlencoded::parse(search.trim_start_matches('?').as_bytes())
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    // Print the log messages as important as the level given as "?log=debug"
+    // to the browser console, or only the warnings and errors. The logger
+    // can only be set once, and is already set if this fails.
+    let level = parameter("log")
+        .and_then(|level| level.trim().parse().ok())
+        .unwrap_or(log::Level::Warn);
+    let _ = console_log::init_with_level(level);
+
+    // Open the station of a shared link, if any.
+    let startup = parameter("station")
+        .map(Startup::Station)
+        .unwrap_or_default();
+
     // Create an application window.
-    eframe::start_web(canvas_id, Box::new(|cc| Box::new(App::new(cc))))
+    eframe::start_web(canvas_id, Box::new(|cc| Box::new(App::new(cc, startup))))
 }
@@ -2,13 +2,131 @@
 #![cfg_attr(not(debug_assertions), deny(warnings))] // Forbid warnings in release builds
 #![warn(clippy::all, rust_2018_idioms)]
 
+/// The ways the application can be started from the command line.
+#[cfg(not(target_arch = "wasm32"))]
+enum Command {
+    /// Open the application window, and play the startup station, if any.
+    Gui(radio::Startup),
+    /// Print the stations matching a name as JSON, then exit.
+    Search(String),
+    /// Play the stream URL without the application window, at the given
+    /// volume, if any, until interrupted.
+    Play(String, Option<i32>),
+}
+
+/// The command line usage shown when the arguments are invalid.
+#[cfg(not(target_arch = "wasm32"))]
+const USAGE: &str = "Usage: radio [--url <stream> | --station <uuid> | --search <name> | \
+                     --play <stream> [--volume <0-100>]]";
+
+/// The name of the application window, under which eframe saves the state.
+#[cfg(not(target_arch = "wasm32"))]
+const APP_NAME: &str = "Online Radio";
+
+/// Return whether the text is an HTTP or HTTPS URL.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_http_url(text: &str) -> bool {
+    text.starts_with("http://") || text.starts_with("https://")
+}
+
+/// Convert the command line arguments (without the program name) into a
+/// command.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args {
+        [] => Ok(Command::Gui(radio::Startup::Nothing)),
+        [flag, url] if flag == "--url" => {
+            if is_http_url(url) {
+                Ok(Command::Gui(radio::Startup::Url(url.to_owned())))
+            } else {
+                Err(format!("'{}' is not an HTTP or HTTPS URL", url))
+            }
+        }
+        [flag, uuid] if flag == "--station" => {
+            if radio::radio_browser::is_station_uuid(uuid) {
+                Ok(Command::Gui(radio::Startup::Station(uuid.to_owned())))
+            } else {
+                Err(format!("'{}' is not a station UUID", uuid))
+            }
+        }
+        [flag, name] if flag == "--search" => Ok(Command::Search(name.to_owned())),
+        // The volume may be given before or after the URL.
+        [flag, url, rest @ ..] | [rest @ .., flag, url] if flag == "--play" => {
+            if !is_http_url(url) {
+                return Err(format!("'{}' is not an HTTP or HTTPS URL", url));
+            }
+            let volume = match rest {
+                [] => None,
+                [flag, level] if flag == "--volume" => match level.parse::<i32>() {
+                    Ok(level) if (0..=100).contains(&level) => Some(level),
+                    _ => return Err(format!("'{}' is not a volume from 0 to 100", level)),
+                },
+                [flag] if flag == "--volume" => return Err("--volume requires a value".to_owned()),
+                [arg, ..] => return Err(format!("unexpected argument '{}'", arg)),
+            };
+            Ok(Command::Play(url.to_owned(), volume))
+        }
+        [flag]
+            if flag == "--url" || flag == "--station" || flag == "--search" || flag == "--play" =>
+        {
+            Err(format!("{} requires a value", flag))
+        }
+        [arg, ..] => Err(format!("unexpected argument '{}'", arg)),
+    }
+}
+
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
-    // Create an application window.
-    let app = radio::App::default();
-    // Get default window properties, such as always-on-top, minimized,
-    // drag-and-drop support, etc.
-    let native_options = eframe::NativeOptions::default();
-    eframe::run_native(Box::new(app), native_options);
+    // Print the log messages chosen in RUST_LOG, such as "debug" or
+    // "radio=debug,eframe=warn", or only the warnings and errors.
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    // Skip the program name, and read the rest of the arguments.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match parse_args(&args) {
+        Ok(Command::Gui(startup)) => {
+            // Log the panics, and save the state before the app closes.
+            radio::install_panic_hook(APP_NAME);
+
+            // Get default window properties, such as always-on-top, minimized,
+            // drag-and-drop support, etc.
+            let native_options = eframe::NativeOptions::default();
+            // Create an application window.
+            eframe::run_native(
+                APP_NAME,
+                native_options,
+                Box::new(|cc| Box::new(radio::App::new(cc, startup))),
+            );
+        }
+        Ok(Command::Search(name)) => {
+            // Search without the application window, and print the stations.
+            let query = radio::radio_browser::SearchQuery::by_name(&name);
+            match radio::radio_browser::search_stations(&query) {
+                Ok(stations) => match serde_json::to_string_pretty(&stations) {
+                    Ok(json) => println!("{}", json),
+                    Err(err) => {
+                        eprintln!("error: {}", err);
+                        std::process::exit(1);
+                    }
+                },
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Ok(Command::Play(url, volume)) => {
+            // Play without the application window, until interrupted.
+            if let Err(err) = radio::play_headless(&url, volume) {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {}\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    }
 }
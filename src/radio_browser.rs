@@ -0,0 +1,629 @@
+use chrono::NaiveDateTime;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Mutex;
+
+/// The hosts of the radio browser servers, which mirror the same database.
+pub const SERVERS: [&str; 3] = [
+    "de1.api.radio-browser.info",
+    "de2.api.radio-browser.info",
+    "fi1.api.radio-browser.info",
+];
+
+/// The host of the radio browser server used for every request, or empty to
+/// use the first server.
+static SERVER: Mutex<String> = Mutex::new(String::new());
+
+/// Send every request to the radio browser server with the given host, or to
+/// the first server if the host is empty.
+pub fn set_server(host: &str) {
+    *SERVER.lock().unwrap() = host.trim().to_owned();
+}
+
+/// Return whether the text is a host name, such as "de1.api.radio-browser.info",
+/// without a scheme, a path, or anything else that cannot be a server.
+pub fn is_valid_host(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && url::Url::parse(&format!("https://{}", text)).is_ok_and(|url| {
+            url.host_str().is_some()
+                && url.username().is_empty()
+                && url.port().is_none()
+                && url.path() == "/"
+                && url.query().is_none()
+                && url.fragment().is_none()
+        })
+}
+
+/// Return the URL of the radio browser server used for every request.
+fn api_url() -> String {
+    let server = SERVER.lock().unwrap();
+    let host = if server.is_empty() {
+        SERVERS[0]
+    } else {
+        server.as_str()
+    };
+    format!("https://{}", host)
+}
+
+/// The most stations a search may retrieve at once.
+pub const MAX_LIMIT: u32 = 1000;
+
+/// The characters encoded in a URL path segment. Letters, digits, and the
+/// unreserved characters are kept.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// The dtata associated to a radio station (url, name, etc).
+// Deriving the deserialization and serialization features is done by the
+// serde_json dependency. These derivations allow JSON text to be converted into
+// a Station struct, and a Station struct to be converted into JSON text.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Station {
+    pub stationuuid: String,
+    pub name: String,
+    pub url: String,
+    pub url_resolved: String,
+    pub homepage: String,
+    pub favicon: String,
+    pub tags: String,
+    pub country: String,
+    pub state: String,
+    pub language: String,
+    pub votes: i32,
+    pub lastchangetime: String,
+    pub codec: String,
+    pub bitrate: u32,
+    pub lastcheckoktime: String,
+    pub clicktimestamp: String,
+    pub clickcount: u32,
+    pub clicktrend: i32,
+}
+
+impl Station {
+    /// Return the URL to stream. The resolved URL skips playlist files, but
+    /// the radio browser leaves it empty for some stations, so the URL given
+    /// by the station is used then.
+    pub fn stream_url(&self) -> &str {
+        if self.url_resolved.is_empty() {
+            &self.url
+        } else {
+            &self.url_resolved
+        }
+    }
+
+    /// Return whether the station streams over HTTPS, which browsers play on
+    /// secure pages, unlike HTTP streams.
+    pub fn is_https(&self) -> bool {
+        self.stream_url()
+            .trim_start()
+            .get(..8)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://"))
+    }
+
+    /// Return whether the radio browser last checked the station as working
+    /// at or after the given time, in seconds since the Unix epoch. Stations
+    /// that never passed a check, or whose check time cannot be read, did
+    /// not.
+    pub fn checked_ok_since(&self, time: i64) -> bool {
+        parse_timestamp(&self.lastcheckoktime).is_some_and(|checked| checked >= time)
+    }
+
+    /// Return the quality of the stream, or None if the station does not tell
+    /// its bitrate or codec.
+    pub fn quality(&self) -> Option<Quality> {
+        Quality::classify(&self.codec, self.bitrate)
+    }
+}
+
+/// How good a stream sounds, judged by its bitrate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quality {
+    /// 256 kbps or more, or a lossless codec.
+    High,
+    /// From 128 to 255 kbps.
+    Standard,
+    /// Below 128 kbps.
+    Low,
+}
+
+impl Quality {
+    /// Classify a stream by its codec and bitrate, in kbps. Lossless streams
+    /// are of high quality whatever their bitrate, and an unknown bitrate of
+    /// 0 tells nothing about the quality.
+    pub fn classify(codec: &str, bitrate: u32) -> Option<Quality> {
+        if codec.trim().eq_ignore_ascii_case("FLAC") {
+            return Some(Quality::High);
+        }
+        match bitrate {
+            0 => None,
+            1..=127 => Some(Quality::Low),
+            128..=255 => Some(Quality::Standard),
+            _ => Some(Quality::High),
+        }
+    }
+
+    /// Return the short name of the quality shown in the badge.
+    pub fn label(self) -> &'static str {
+        match self {
+            Quality::High => "HD",
+            Quality::Standard => "SD",
+            Quality::Low => "LD",
+        }
+    }
+}
+
+/// Convert a radio browser timestamp, such as "2022-08-06 12:34:56", into the
+/// seconds since the Unix epoch. The timestamps are in UTC, and the ISO 8601
+/// form "2022-08-06T12:34:56Z" is read too. Return None for text that is not
+/// a timestamp.
+pub fn parse_timestamp(text: &str) -> Option<i64> {
+    let text = text.trim().trim_end_matches('Z');
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(|time| time.and_utc().timestamp())
+}
+
+/// The station field that is searched.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub enum SearchBy {
+    /// Search by station name.
+    #[default]
+    Name,
+    /// Search by tag, such as a genre like "jazz".
+    Tag,
+    /// Search by country name.
+    Country,
+    /// Search by language.
+    Language,
+    /// Search by the two-letter code of the country, such as "DE", which
+    /// must match exactly.
+    CountryCode,
+}
+
+impl SearchBy {
+    /// Every search field, in the order they are shown.
+    pub const ALL: [SearchBy; 4] = [
+        SearchBy::Name,
+        SearchBy::Tag,
+        SearchBy::Country,
+        SearchBy::Language,
+    ];
+
+    /// Return the parameter of the advanced search that searches by this
+    /// field.
+    fn search_parameter(self) -> &'static str {
+        match self {
+            SearchBy::Name => "name",
+            SearchBy::Tag => "tag",
+            SearchBy::Country => "country",
+            SearchBy::Language => "language",
+            SearchBy::CountryCode => "countrycode",
+        }
+    }
+
+    /// Return the radio browser endpoint that searches by this field.
+    fn endpoint(self) -> &'static str {
+        match self {
+            SearchBy::Name => "byname",
+            SearchBy::Tag => "bytag",
+            SearchBy::Country => "bycountry",
+            SearchBy::Language => "bylanguage",
+            SearchBy::CountryCode => "bycountrycodeexact",
+        }
+    }
+}
+
+/// The orders in which the radio browser can return the stations of a search.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub enum SearchOrder {
+    /// The order of the radio browser.
+    #[default]
+    Relevance,
+    /// The most voted stations first.
+    Votes,
+    /// The most played stations first.
+    Clicks,
+    /// The highest bitrate first.
+    Bitrate,
+    /// By name, alphabetically.
+    Name,
+}
+
+impl SearchOrder {
+    /// Every order, in the order they are shown.
+    pub const ALL: [SearchOrder; 5] = [
+        SearchOrder::Relevance,
+        SearchOrder::Votes,
+        SearchOrder::Clicks,
+        SearchOrder::Bitrate,
+        SearchOrder::Name,
+    ];
+
+    /// Return the English name of the order.
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchOrder::Relevance => "Relevance",
+            SearchOrder::Votes => "Votes",
+            SearchOrder::Clicks => "Clicks",
+            SearchOrder::Bitrate => "Bitrate",
+            SearchOrder::Name => "Name",
+        }
+    }
+
+    /// Return the order parameter of the advanced search, and whether the
+    /// order is reversed, or None to keep the order of the radio browser.
+    fn parameters(self) -> Option<(&'static str, bool)> {
+        match self {
+            SearchOrder::Relevance => None,
+            SearchOrder::Votes => Some(("votes", true)),
+            SearchOrder::Clicks => Some(("clickcount", true)),
+            SearchOrder::Bitrate => Some(("bitrate", true)),
+            SearchOrder::Name => Some(("name", false)),
+        }
+    }
+}
+
+/// The parameters used to search for stations in the radio browser database.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    /// The station field that is searched.
+    pub by: SearchBy,
+
+    /// The searched text, such as the station name or part of it.
+    pub text: String,
+
+    /// The maximum number of stations to retrieve, from 1 to MAX_LIMIT.
+    pub limit: u32,
+
+    /// The number of matching stations to skip, to retrieve the next pages.
+    pub offset: u32,
+
+    /// Whether to exclude the stations the radio browser knows are broken.
+    pub hide_broken: bool,
+
+    /// The lowest bitrate of the stations in kbps, or 0 for any bitrate.
+    pub min_bitrate: u32,
+
+    /// The codec of the stations, such as "MP3", or empty for any codec.
+    pub codec: String,
+
+    /// The other fields the stations must match, such as their country when
+    /// searching by tag. Empty texts match every station.
+    pub filters: Vec<(SearchBy, String)>,
+
+    /// The order in which the radio browser returns the stations.
+    pub order: SearchOrder,
+}
+
+impl SearchQuery {
+    /// Create a query that searches stations by the given field.
+    pub fn new(by: SearchBy, text: &str) -> Self {
+        SearchQuery {
+            by,
+            text: text.to_owned(),
+            limit: 100,
+            offset: 0,
+            hide_broken: true,
+            min_bitrate: 0,
+            codec: String::new(),
+            filters: Vec::new(),
+            order: SearchOrder::Relevance,
+        }
+    }
+
+    /// Create a query that searches stations by name.
+    pub fn by_name(name: &str) -> Self {
+        SearchQuery::new(SearchBy::Name, name)
+    }
+
+    /// Return the number of stations retrieved at once, which is the limit
+    /// kept from 1 to MAX_LIMIT.
+    pub fn page_size(&self) -> u32 {
+        self.limit.clamp(1, MAX_LIMIT)
+    }
+
+    /// Skip the stations of the current page, so the request retrieves the
+    /// next page.
+    pub fn next_page(&mut self) {
+        self.offset += self.page_size();
+    }
+
+    /// Return whether the search uses parameters besides the searched field,
+    /// which only the advanced search accepts.
+    fn is_advanced(&self) -> bool {
+        self.min_bitrate > 0
+            || !self.codec.trim().is_empty()
+            || self.order != SearchOrder::Relevance
+            || self.filters.iter().any(|(_, text)| !text.trim().is_empty())
+    }
+}
+
+/// Create the HTTP request that retrieves the stations of the search. The
+/// searched text is trimmed and encoded, so spaces, slashes, and characters
+/// like # or ? are searched instead of breaking the URL. Searches that combine
+/// several parameters, such as a tag, a country, and an order, post them to
+/// the advanced search as a form instead.
+pub fn build_search_request(query: &SearchQuery) -> ehttp::Request {
+    if !query.is_advanced() {
+        return ehttp::Request::get(format!(
+            "{}/json/stations/{}/{}?limit={}&offset={}&hidebroken={}",
+            api_url(),
+            query.by.endpoint(),
+            utf8_percent_encode(query.text.trim(), PATH_SEGMENT),
+            query.page_size(),
+            query.offset,
+            query.hide_broken
+        ));
+    }
+
+    let mut form = url::form_urlencoded::Serializer::new(String::new());
+    form.append_pair(query.by.search_parameter(), query.text.trim())
+        .append_pair("limit", &query.page_size().to_string())
+        .append_pair("offset", &query.offset.to_string())
+        .append_pair("hidebroken", &query.hide_broken.to_string());
+    // The searched field is matched with the searched text, so a filter of
+    // the same field is left out.
+    for (by, text) in &query.filters {
+        if *by != query.by && !text.trim().is_empty() {
+            form.append_pair(by.search_parameter(), text.trim());
+        }
+    }
+    if query.min_bitrate > 0 {
+        form.append_pair("bitrateMin", &query.min_bitrate.to_string());
+    }
+    if !query.codec.trim().is_empty() {
+        form.append_pair("codec", query.codec.trim());
+    }
+    if let Some((order, reverse)) = query.order.parameters() {
+        form.append_pair("order", order)
+            .append_pair("reverse", &reverse.to_string());
+    }
+
+    let mut request = ehttp::Request::post(
+        format!("{}/json/stations/search", api_url()),
+        form.finish().into_bytes(),
+    );
+    request.headers.insert(
+        "Content-Type".to_owned(),
+        "application/x-www-form-urlencoded".to_owned(),
+    );
+    request
+}
+
+/// Return whether the text looks like a station UUID, which only holds
+/// hexadecimal digits and dashes.
+pub fn is_station_uuid(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// Create the HTTP request that retrieves the station with the given UUID.
+pub fn station_by_uuid_request(uuid: &str) -> ehttp::Request {
+    ehttp::Request::get(format!(
+        "{}/json/stations/byuuid/{}",
+        api_url(),
+        utf8_percent_encode(uuid, PATH_SEGMENT)
+    ))
+}
+
+/// Create the HTTP request that tells the radio browser the station with the
+/// given UUID was played, which increases its click count.
+pub fn station_click_request(uuid: &str) -> ehttp::Request {
+    ehttp::Request::post(format!("{}/json/url/{}", api_url(), uuid), Vec::new())
+}
+
+/// Create the HTTP request that votes for the station with the given UUID. The
+/// radio browser only counts one vote per station and address every ten
+/// minutes.
+pub fn station_vote_request(uuid: &str) -> ehttp::Request {
+    ehttp::Request::post(format!("{}/json/vote/{}", api_url(), uuid), Vec::new())
+}
+
+/// The reply of the radio browser to a vote.
+#[derive(Deserialize, Debug)]
+pub struct VoteReply {
+    /// Whether the vote was counted.
+    pub ok: bool,
+    /// Why the vote was not counted, or a confirmation that it was.
+    pub message: String,
+}
+
+/// The errors that may happen while searching for stations.
+#[derive(Debug)]
+pub enum Error {
+    /// The HTTP request could not be done.
+    Http(ehttp::Error),
+    /// The server replied, but with an unsuccessful status code.
+    Status(u16, String),
+    /// The response could not be converted into stations.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(err) => write!(f, "{}", err),
+            Error::Status(status, status_text) => write!(f, "{} {}", status, status_text),
+            Error::Json(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Convert the JSON text returned by the radio browser into stations.
+pub fn parse_stations(text: &str) -> Result<Vec<Station>, serde_json::Error> {
+    serde_json::from_str::<Vec<Station>>(text)
+}
+
+/// Convert the response of the radio browser to a search into stations. The
+/// request may have failed, or the server may have replied with an
+/// unsuccessful status code or with text that is not stations, and then the
+/// error tells which.
+pub fn handle_response(
+    response: Result<ehttp::Response, ehttp::Error>,
+) -> Result<Vec<Station>, Error> {
+    let response = response.map_err(Error::Http)?;
+
+    // HTTP errors like 404 are not considered errors by ehttp.
+    if !response.ok {
+        return Err(Error::Status(response.status, response.status_text));
+    }
+
+    // A response without text contains no stations.
+    match response.text() {
+        Some(text) => parse_stations(text).map_err(Error::Json),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Convert the JSON text returned by the radio browser after a vote into a
+/// reply.
+pub fn parse_vote_reply(text: &str) -> Result<VoteReply, serde_json::Error> {
+    serde_json::from_str::<VoteReply>(text)
+}
+
+/// Search for stations and wait for the response. Only available when
+/// compiling natively, since the web cannot block while waiting.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn search_stations(query: &SearchQuery) -> Result<Vec<Station>, Error> {
+    handle_response(ehttp::fetch_blocking(&build_search_request(query)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return a station with every field set, so a field lost on the way is
+    /// noticed.
+    fn full_station() -> Station {
+        Station {
+            stationuuid: "96062a7b-0601-11e8-ae97-52543be04c81".to_owned(),
+            name: "Jazz FM".to_owned(),
+            url: "http://jazz.example.com/stream.m3u".to_owned(),
+            url_resolved: "http://jazz.example.com/stream".to_owned(),
+            homepage: "https://jazz.example.com".to_owned(),
+            favicon: "https://jazz.example.com/logo.png".to_owned(),
+            tags: "jazz,blues".to_owned(),
+            country: "Germany".to_owned(),
+            state: "Berlin".to_owned(),
+            language: "german".to_owned(),
+            votes: 42,
+            lastchangetime: "2022-08-06 12:34:56".to_owned(),
+            codec: "MP3".to_owned(),
+            bitrate: 128,
+            lastcheckoktime: "2022-08-07 01:02:03".to_owned(),
+            clicktimestamp: "2022-08-07 04:05:06".to_owned(),
+            clickcount: 1000,
+            clicktrend: -3,
+        }
+    }
+
+    /// Return a response of the radio browser with the given status and
+    /// text.
+    fn response(status: u16, text: &str) -> Result<ehttp::Response, ehttp::Error> {
+        Ok(ehttp::Response {
+            url: "https://de1.api.radio-browser.info/json/stations/byname/jazz".to_owned(),
+            ok: (200..300).contains(&status),
+            status,
+            status_text: if status == 200 { "OK" } else { "Not Found" }.to_owned(),
+            bytes: text.as_bytes().to_vec(),
+            headers: Default::default(),
+        })
+    }
+
+    #[test]
+    fn successful_response_is_converted_into_stations() {
+        let station = full_station();
+        let text = serde_json::to_string(&[&station]).unwrap();
+        let stations = handle_response(response(200, &text)).unwrap();
+        assert_eq!(stations, vec![station]);
+    }
+
+    #[test]
+    fn unsuccessful_status_is_an_error() {
+        let result = handle_response(response(404, "[]"));
+        assert!(matches!(result, Err(Error::Status(404, _))));
+    }
+
+    #[test]
+    fn malformed_json_is_an_error() {
+        let result = handle_response(response(200, "[{\"name\": "));
+        assert!(matches!(result, Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn empty_array_has_no_stations() {
+        assert_eq!(handle_response(response(200, "[]")).unwrap(), Vec::new());
+    }
+
+    /// Return the path segment of the simple search request that holds the
+    /// searched text.
+    fn searched_segment(text: &str) -> String {
+        let request = build_search_request(&SearchQuery::by_name(text));
+        let path = request.url.split('?').next().unwrap();
+        path.rsplit('/').next().unwrap().to_owned()
+    }
+
+    #[test]
+    fn searched_text_is_one_encoded_path_segment() {
+        assert_eq!(searched_segment("jazz & blues"), "jazz%20%26%20blues");
+        assert_eq!(searched_segment("rock/pop"), "rock%2Fpop");
+    }
+
+    #[test]
+    fn searched_text_is_trimmed() {
+        assert_eq!(searched_segment("  jazz\t "), "jazz");
+    }
+
+    #[test]
+    fn quality_changes_at_the_bitrate_boundaries() {
+        assert_eq!(Quality::classify("MP3", 127), Some(Quality::Low));
+        assert_eq!(Quality::classify("MP3", 128), Some(Quality::Standard));
+        assert_eq!(Quality::classify("MP3", 255), Some(Quality::Standard));
+        assert_eq!(Quality::classify("MP3", 256), Some(Quality::High));
+        assert_eq!(Quality::classify("MP3", 0), None);
+    }
+
+    #[test]
+    fn lossless_streams_are_high_quality_at_any_bitrate() {
+        assert_eq!(Quality::classify("FLAC", 64), Some(Quality::High));
+        assert_eq!(Quality::classify(" flac ", 0), Some(Quality::High));
+    }
+
+    #[test]
+    fn station_survives_a_json_round_trip() {
+        let station = full_station();
+        let json = serde_json::to_string(&station).unwrap();
+        let parsed: Station = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, station);
+    }
+
+    #[test]
+    fn timestamps_are_read_as_seconds_since_the_epoch() {
+        assert_eq!(parse_timestamp("1970-01-01 00:00:00"), Some(0));
+        assert_eq!(parse_timestamp("2022-08-06 12:34:56"), Some(1_659_789_296));
+        assert_eq!(
+            parse_timestamp(" 2022-08-06T12:34:56Z "),
+            Some(1_659_789_296)
+        );
+    }
+
+    #[test]
+    fn leap_days_are_counted() {
+        assert_eq!(parse_timestamp("2020-02-29 00:00:00"), Some(1_582_934_400));
+        assert_eq!(parse_timestamp("2020-03-01 00:00:00"), Some(1_583_020_800));
+        assert_eq!(parse_timestamp("2021-02-29 00:00:00"), None);
+    }
+
+    #[test]
+    fn malformed_timestamps_are_rejected() {
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("2022-08-06"), None);
+        assert_eq!(parse_timestamp("2022-13-01 00:00:00"), None);
+        assert_eq!(parse_timestamp("2022-08-06 24:00:00"), None);
+    }
+}